@@ -0,0 +1,458 @@
+#[cfg(test)]
+mod tests;
+
+use crate::discrete::Discrete;
+use crate::element::Element;
+use crate::set::RangedSet;
+use crate::span::Span;
+use crate::view::Pairs;
+use std::iter::{FusedIterator, Peekable};
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign,
+};
+use std::{ops, slice};
+
+/// The spans of either an owned set or a view over a buffer, so the
+/// sweeps below can combine the two.
+#[derive(Clone, Debug)]
+pub(crate) enum Spans<'a, T: Discrete> {
+    Elements(slice::Iter<'a, Element<T>>),
+    Pairs(Pairs<'a, T>),
+}
+
+impl<'a, T: Discrete> Iterator for Spans<'a, T> {
+    type Item = Span<T>;
+
+    fn next(&mut self) -> Option<Span<T>> {
+        match self {
+            Spans::Elements(elements) => elements
+                .next()
+                .map(|e| Span::new(e.start().clone(), e.end().clone())),
+            Spans::Pairs(pairs) => pairs.next(),
+        }
+    }
+}
+
+fn touches<T: Discrete>(end: &T, start: &T) -> bool {
+    start <= end || end.successor().as_ref() == Some(start)
+}
+
+fn into_std<T>(range: Span<T>) -> ops::RangeInclusive<T> {
+    range.start..=range.end
+}
+
+/// Merges two sorted streams of spans, joining any that overlap or
+/// are adjacent.
+#[derive(Clone, Debug)]
+pub struct UnionSweep<T, A, B>
+where
+    A: Iterator<Item = Span<T>>,
+    B: Iterator<Item = Span<T>>,
+{
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<T, A, B> UnionSweep<T, A, B>
+where
+    T: Discrete,
+    A: Iterator<Item = Span<T>>,
+    B: Iterator<Item = Span<T>>,
+{
+    pub fn new(a: A, b: B) -> Self {
+        UnionSweep {
+            a: a.peekable(),
+            b: b.peekable(),
+        }
+    }
+}
+
+impl<T, A, B> Iterator for UnionSweep<T, A, B>
+where
+    T: Discrete,
+    A: Iterator<Item = Span<T>>,
+    B: Iterator<Item = Span<T>>,
+{
+    type Item = Span<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a_first = match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) => a.start <= b.start,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => return None,
+        };
+        let mut current = if a_first {
+            self.a.next()
+        } else {
+            self.b.next()
+        }?;
+
+        // Keep swallowing spans from either side until neither of them
+        // has one that reaches the current span.
+        loop {
+            let next = match (self.a.peek(), self.b.peek()) {
+                (Some(a), _) if touches(&current.end, &a.start) => self.a.next(),
+                (_, Some(b)) if touches(&current.end, &b.start) => self.b.next(),
+                _ => break,
+            };
+
+            if let Some(next) = next {
+                if current.end < next.end {
+                    current.end = next.end;
+                }
+            }
+        }
+
+        Some(current)
+    }
+}
+
+/// Yields the parts of two sorted streams of spans that overlap.
+#[derive(Clone, Debug)]
+pub struct IntersectionSweep<T, A, B>
+where
+    A: Iterator<Item = Span<T>>,
+    B: Iterator<Item = Span<T>>,
+{
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<T, A, B> IntersectionSweep<T, A, B>
+where
+    T: Discrete,
+    A: Iterator<Item = Span<T>>,
+    B: Iterator<Item = Span<T>>,
+{
+    pub fn new(a: A, b: B) -> Self {
+        IntersectionSweep {
+            a: a.peekable(),
+            b: b.peekable(),
+        }
+    }
+}
+
+impl<T, A, B> Iterator for IntersectionSweep<T, A, B>
+where
+    T: Discrete,
+    A: Iterator<Item = Span<T>>,
+    B: Iterator<Item = Span<T>>,
+{
+    type Item = Span<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (start, end, a_ends_first) = {
+                let a = self.a.peek()?;
+                let b = self.b.peek()?;
+
+                (
+                    std::cmp::max(&a.start, &b.start).clone(),
+                    std::cmp::min(&a.end, &b.end).clone(),
+                    a.end <= b.end,
+                )
+            };
+
+            // Whichever span ends first can't overlap anything else on
+            // the other side.
+            if a_ends_first {
+                self.a.next();
+            } else {
+                self.b.next();
+            }
+
+            if start <= end {
+                return Some(Span::new(start, end));
+            }
+        }
+    }
+}
+
+/// Yields the parts of one sorted stream of spans that aren't covered
+/// by another.
+#[derive(Clone, Debug)]
+pub struct DifferenceSweep<T, A, B>
+where
+    A: Iterator<Item = Span<T>>,
+    B: Iterator<Item = Span<T>>,
+{
+    current: Option<Span<T>>,
+    a: A,
+    b: Peekable<B>,
+}
+
+impl<T, A, B> DifferenceSweep<T, A, B>
+where
+    T: Discrete,
+    A: Iterator<Item = Span<T>>,
+    B: Iterator<Item = Span<T>>,
+{
+    pub fn new(a: A, b: B) -> Self {
+        DifferenceSweep {
+            current: None,
+            a,
+            b: b.peekable(),
+        }
+    }
+}
+
+impl<T, A, B> Iterator for DifferenceSweep<T, A, B>
+where
+    T: Discrete,
+    A: Iterator<Item = Span<T>>,
+    B: Iterator<Item = Span<T>>,
+{
+    type Item = Span<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let current = match self.current.take() {
+                Some(current) => current,
+                None => self.a.next()?,
+            };
+
+            while self.b.peek().map_or(false, |b| b.end < current.start) {
+                self.b.next();
+            }
+
+            let (start, end) = match self.b.peek() {
+                Some(b) if b.start <= current.end => (b.start.clone(), b.end.clone()),
+                _ => return Some(current),
+            };
+
+            // The span being removed may only cover part of the current
+            // one, so hold on to whatever is left after it. It stays in
+            // `b` as it may also cover part of the next span from `a`.
+            if end < current.end {
+                self.current = end.successor().map(|n| Span::new(n, current.end.clone()));
+            }
+
+            if current.start < start {
+                if let Some(p) = start.predecessor() {
+                    return Some(Span::new(current.start, p));
+                }
+            }
+        }
+    }
+}
+
+/// Yields the spans in one of two sorted streams but not both.
+pub type SymmetricDifferenceSweep<T, A, B> =
+    UnionSweep<T, DifferenceSweep<T, A, B>, DifferenceSweep<T, B, A>>;
+
+pub fn symmetric_difference<T, A, B>(a: A, b: B) -> SymmetricDifferenceSweep<T, A, B>
+where
+    T: Discrete,
+    A: Iterator<Item = Span<T>> + Clone,
+    B: Iterator<Item = Span<T>> + Clone,
+{
+    UnionSweep::new(
+        DifferenceSweep::new(a.clone(), b.clone()),
+        DifferenceSweep::new(b, a),
+    )
+}
+
+/// A lazy iterator over the ranges of values in either of two sets
+///
+/// This `struct` is created by the [`union`] method on [`RangedSet`].
+///
+/// [`union`]: struct.RangedSet.html#method.union
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Debug)]
+pub struct Union<'a, T: Discrete> {
+    sweep: UnionSweep<T, Spans<'a, T>, Spans<'a, T>>,
+}
+
+impl<'a, T: Discrete> Union<'a, T> {
+    pub(crate) fn new(a: Spans<'a, T>, b: Spans<'a, T>) -> Self {
+        Union {
+            sweep: UnionSweep::new(a, b),
+        }
+    }
+}
+
+impl<'a, T: Discrete> Iterator for Union<'a, T> {
+    type Item = ops::RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sweep.next().map(into_std)
+    }
+}
+
+impl<'a, T: Discrete> FusedIterator for Union<'a, T> {}
+
+/// A lazy iterator over the ranges of values in both of two sets
+///
+/// This `struct` is created by the [`intersection`] method on
+/// [`RangedSet`].
+///
+/// [`intersection`]: struct.RangedSet.html#method.intersection
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Debug)]
+pub struct Intersection<'a, T: Discrete> {
+    sweep: IntersectionSweep<T, Spans<'a, T>, Spans<'a, T>>,
+}
+
+impl<'a, T: Discrete> Intersection<'a, T> {
+    pub(crate) fn new(a: Spans<'a, T>, b: Spans<'a, T>) -> Self {
+        Intersection {
+            sweep: IntersectionSweep::new(a, b),
+        }
+    }
+}
+
+impl<'a, T: Discrete> Iterator for Intersection<'a, T> {
+    type Item = ops::RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sweep.next().map(into_std)
+    }
+}
+
+impl<'a, T: Discrete> FusedIterator for Intersection<'a, T> {}
+
+/// A lazy iterator over the ranges of values in one set but not another
+///
+/// This `struct` is created by the [`difference`] method on
+/// [`RangedSet`].
+///
+/// [`difference`]: struct.RangedSet.html#method.difference
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Debug)]
+pub struct Difference<'a, T: Discrete> {
+    sweep: DifferenceSweep<T, Spans<'a, T>, Spans<'a, T>>,
+}
+
+impl<'a, T: Discrete> Difference<'a, T> {
+    pub(crate) fn new(a: Spans<'a, T>, b: Spans<'a, T>) -> Self {
+        Difference {
+            sweep: DifferenceSweep::new(a, b),
+        }
+    }
+}
+
+impl<'a, T: Discrete> Iterator for Difference<'a, T> {
+    type Item = ops::RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sweep.next().map(into_std)
+    }
+}
+
+impl<'a, T: Discrete> FusedIterator for Difference<'a, T> {}
+
+/// A lazy iterator over the ranges of values in exactly one of two sets
+///
+/// This `struct` is created by the [`symmetric_difference`] method on
+/// [`RangedSet`].
+///
+/// [`symmetric_difference`]: struct.RangedSet.html#method.symmetric_difference
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Debug)]
+pub struct SymmetricDifference<'a, T: Discrete> {
+    sweep: SymmetricDifferenceSweep<T, Spans<'a, T>, Spans<'a, T>>,
+}
+
+impl<'a, T: Discrete> SymmetricDifference<'a, T> {
+    pub(crate) fn new(a: Spans<'a, T>, b: Spans<'a, T>) -> Self {
+        SymmetricDifference {
+            sweep: symmetric_difference(a, b),
+        }
+    }
+}
+
+impl<'a, T: Discrete> Iterator for SymmetricDifference<'a, T> {
+    type Item = ops::RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.sweep.next().map(into_std)
+    }
+}
+
+impl<'a, T: Discrete> FusedIterator for SymmetricDifference<'a, T> {}
+
+macro_rules! set_operator {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $method:ident, $with:ident) => {
+        impl<'a, 'b, T: Discrete> $op<&'b RangedSet<T>> for &'a RangedSet<T> {
+            type Output = RangedSet<T>;
+
+            fn $op_fn(self, rhs: &'b RangedSet<T>) -> RangedSet<T> {
+                RangedSet::from_canonical_ranges(self.$method(rhs))
+            }
+        }
+
+        impl<'a, T: Discrete> $op<RangedSet<T>> for &'a RangedSet<T> {
+            type Output = RangedSet<T>;
+
+            fn $op_fn(self, rhs: RangedSet<T>) -> RangedSet<T> {
+                RangedSet::from_canonical_ranges(self.$method(&rhs))
+            }
+        }
+
+        impl<'b, T: Discrete> $op<&'b RangedSet<T>> for RangedSet<T> {
+            type Output = RangedSet<T>;
+
+            fn $op_fn(mut self, rhs: &'b RangedSet<T>) -> RangedSet<T> {
+                self.$with(rhs);
+                self
+            }
+        }
+
+        impl<T: Discrete> $op<RangedSet<T>> for RangedSet<T> {
+            type Output = RangedSet<T>;
+
+            fn $op_fn(mut self, rhs: RangedSet<T>) -> RangedSet<T> {
+                self.$with(&rhs);
+                self
+            }
+        }
+
+        impl<'b, T: Discrete> $assign<&'b RangedSet<T>> for RangedSet<T> {
+            fn $assign_fn(&mut self, rhs: &'b RangedSet<T>) {
+                self.$with(rhs);
+            }
+        }
+
+        impl<T: Discrete> $assign<RangedSet<T>> for RangedSet<T> {
+            fn $assign_fn(&mut self, rhs: RangedSet<T>) {
+                self.$with(&rhs);
+            }
+        }
+    };
+}
+
+set_operator!(BitOr, bitor, BitOrAssign, bitor_assign, union, union_with);
+set_operator!(
+    BitAnd,
+    bitand,
+    BitAndAssign,
+    bitand_assign,
+    intersection,
+    intersection_with
+);
+set_operator!(Sub, sub, SubAssign, sub_assign, difference, difference_with);
+set_operator!(
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    symmetric_difference,
+    symmetric_difference_with
+);
+
+impl<T: Discrete> Not for &RangedSet<T> {
+    type Output = RangedSet<T>;
+
+    fn not(self) -> RangedSet<T> {
+        self.complement()
+    }
+}
+
+impl<T: Discrete> Not for RangedSet<T> {
+    type Output = RangedSet<T>;
+
+    fn not(self) -> RangedSet<T> {
+        self.complement()
+    }
+}
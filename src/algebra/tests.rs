@@ -0,0 +1,145 @@
+use crate::algebra::{DifferenceSweep, IntersectionSweep, UnionSweep, symmetric_difference};
+use crate::span::Span;
+
+fn spans<T: Clone>(bounds: &[(T, T)]) -> std::vec::IntoIter<Span<T>> {
+    bounds
+        .iter()
+        .map(|(s, e)| Span::new(s.clone(), e.clone()))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+#[test]
+fn union_with_empty_side() {
+    assert_eq!(
+        UnionSweep::new(spans(&[(0, 2), (5, 5)]), spans(&[])).collect::<Vec<_>>(),
+        spans(&[(0, 2), (5, 5)]).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        UnionSweep::new(spans(&[]), spans(&[(0, 2), (5, 5)])).collect::<Vec<_>>(),
+        spans(&[(0, 2), (5, 5)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn union_of_disjoint_spans() {
+    assert_eq!(
+        UnionSweep::new(spans(&[(0, 1), (10, 12)]), spans(&[(4, 6), (20, 20)])).collect::<Vec<_>>(),
+        spans(&[(0, 1), (4, 6), (10, 12), (20, 20)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn union_of_adjacent_spans() {
+    assert_eq!(
+        UnionSweep::new(spans(&[(0, 3), (8, 9)]), spans(&[(4, 7), (10, 12)])).collect::<Vec<_>>(),
+        spans(&[(0, 12)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn union_of_overlapping_spans() {
+    assert_eq!(
+        UnionSweep::new(
+            spans(&[(0, 5), (7, 9), (20, 30)]),
+            spans(&[(2, 8), (22, 25)])
+        )
+        .collect::<Vec<_>>(),
+        spans(&[(0, 9), (20, 30)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn union_at_type_limits() {
+    assert_eq!(
+        UnionSweep::new(spans(&[(0u8, 100), (250, 255)]), spans(&[(101u8, 255)]))
+            .collect::<Vec<_>>(),
+        spans(&[(0u8, 255)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn intersection_of_disjoint_spans() {
+    assert_eq!(
+        IntersectionSweep::new(spans(&[(0, 1), (10, 12)]), spans(&[(2, 9), (13, 20)]))
+            .collect::<Vec<_>>(),
+        spans::<i32>(&[]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn intersection_of_overlapping_spans() {
+    assert_eq!(
+        IntersectionSweep::new(
+            spans(&[(0, 5), (8, 12), (15, 15)]),
+            spans(&[(3, 9), (11, 20)])
+        )
+        .collect::<Vec<_>>(),
+        spans(&[(3, 5), (8, 9), (11, 12), (15, 15)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn intersection_of_spans_ending_together() {
+    assert_eq!(
+        IntersectionSweep::new(spans(&[(0, 5), (7, 9)]), spans(&[(2, 5), (6, 9)]))
+            .collect::<Vec<_>>(),
+        spans(&[(2, 5), (7, 9)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn difference_with_nothing_removed() {
+    assert_eq!(
+        DifferenceSweep::new(spans(&[(0, 3), (8, 9)]), spans(&[(4, 7), (10, 20)]))
+            .collect::<Vec<_>>(),
+        spans(&[(0, 3), (8, 9)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn difference_splitting_spans() {
+    assert_eq!(
+        DifferenceSweep::new(spans(&[(0, 20)]), spans(&[(3, 4), (8, 8), (15, 30)]))
+            .collect::<Vec<_>>(),
+        spans(&[(0, 2), (5, 7), (9, 14)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn difference_with_span_covering_several() {
+    assert_eq!(
+        DifferenceSweep::new(spans(&[(0, 3), (5, 6), (8, 12)]), spans(&[(2, 9)]))
+            .collect::<Vec<_>>(),
+        spans(&[(0, 1), (10, 12)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn difference_at_type_limits() {
+    assert_eq!(
+        DifferenceSweep::new(spans(&[(0u8, 255)]), spans(&[(0u8, 0), (255, 255)]))
+            .collect::<Vec<_>>(),
+        spans(&[(1u8, 254)]).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        DifferenceSweep::new(spans(&[(0u8, 255)]), spans(&[(0u8, 255)])).collect::<Vec<_>>(),
+        spans::<u8>(&[]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn symmetric_difference_of_overlapping_spans() {
+    assert_eq!(
+        symmetric_difference(spans(&[(0, 5), (10, 12)]), spans(&[(3, 11)])).collect::<Vec<_>>(),
+        spans(&[(0, 2), (6, 9), (12, 12)]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn symmetric_difference_of_adjacent_spans() {
+    assert_eq!(
+        symmetric_difference(spans(&[(0, 3)]), spans(&[(4, 7)])).collect::<Vec<_>>(),
+        spans(&[(0, 7)]).collect::<Vec<_>>()
+    );
+}
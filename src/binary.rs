@@ -0,0 +1,338 @@
+#[cfg(test)]
+mod tests;
+
+use crate::encodable::Encodable;
+use crate::set::RangedSet;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+/// The bytes every encoded set starts with
+const MAGIC: [u8; 4] = *b"RSET";
+
+/// The version of the format written by `encode_to`
+const VERSION: u8 = 1;
+
+/// The most bytes a variable-length `u128` can take up
+const MAX_VARINT_LEN: usize = 19;
+
+impl<T: Encodable> RangedSet<T> {
+    /// Writes the set in a compact binary format
+    ///
+    /// The encoding starts with a header holding a magic number, the
+    /// format version and a tag for the type of value, followed by the
+    /// number of ranges in the set. Each range is then written as the
+    /// gap since the end of the previous range and the length of the
+    /// range, both as LEB128 variable-length integers; the first range
+    /// is written as its start instead of a gap. A CRC-32 checksum of
+    /// everything before it ends the encoding.
+    ///
+    /// Sets of values that are close together, like sequence numbers,
+    /// take up only a few bytes per range. Lots of small writes are
+    /// made, so a `BufWriter` is worth using for files and sockets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let set: RangedSet<u64> = vec![1000..=1999, 2100..=2100].into_iter().collect();
+    ///
+    /// let mut bytes = Vec::new();
+    /// set.encode_to(&mut bytes).unwrap();
+    /// assert_eq!(bytes.len(), 17);
+    ///
+    /// let decoded = RangedSet::decode_from(&bytes[..]).unwrap();
+    /// assert_eq!(decoded, set);
+    /// ```
+    pub fn encode_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut writer = Checksummed::new(writer);
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION, T::TAG])?;
+        write_varint(&mut writer, self.range_count() as u128)?;
+
+        let mut previous: Option<T> = None;
+        for range in self.ranges() {
+            let (start, end) = range.into_inner();
+
+            match previous {
+                Some(previous) => write_varint(&mut writer, previous.distance(&start) - 1)?,
+                None => write_varint(&mut writer, start.to_bits())?,
+            }
+            write_varint(&mut writer, start.distance(&end))?;
+
+            previous = Some(end);
+        }
+
+        let checksum = writer.checksum();
+        writer.inner.write_all(&checksum.to_le_bytes())
+    }
+
+    /// Reads a set written by [`encode_to`]
+    ///
+    /// The input is checked strictly: it has to start with the right
+    /// header for the type being read, every range has to be in
+    /// canonical form (sorted, with gaps between them, and no padded
+    /// integers), and the checksum has to match. Nothing past the end
+    /// of the encoding is read. Lots of small reads are made, so a
+    /// `BufReader` is worth using for files and sockets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::{DecodeError, RangedSet};
+    ///
+    /// let set: RangedSet<i32> = vec![-5..=5].into_iter().collect();
+    ///
+    /// let mut bytes = Vec::new();
+    /// set.encode_to(&mut bytes).unwrap();
+    ///
+    /// let truncated = RangedSet::<i32>::decode_from(&bytes[..bytes.len() - 1]);
+    /// assert!(matches!(truncated, Err(DecodeError::Truncated)));
+    ///
+    /// let wrong_type = RangedSet::<u32>::decode_from(&bytes[..]);
+    /// assert!(matches!(wrong_type, Err(DecodeError::WrongType)));
+    /// ```
+    ///
+    /// [`encode_to`]: #method.encode_to
+    pub fn decode_from<R: Read>(reader: R) -> Result<Self, DecodeError> {
+        let mut reader = Checksummed::new(reader);
+
+        let mut magic = [0; 4];
+        read_exact(&mut reader, &mut magic)?;
+        if magic != MAGIC {
+            return Err(DecodeError::BadMagic);
+        }
+
+        let mut header = [0; 2];
+        read_exact(&mut reader, &mut header)?;
+        if header[0] != VERSION {
+            return Err(DecodeError::UnsupportedVersion(header[0]));
+        }
+        if header[1] != T::TAG {
+            return Err(DecodeError::WrongType);
+        }
+
+        let count = read_varint(&mut reader)?;
+
+        let mut set = RangedSet::new();
+        let mut previous: Option<T> = None;
+        for _ in 0..count {
+            let start = match previous {
+                Some(previous) => {
+                    // A gap of zero would mean the range touches the
+                    // previous one, which should have been merged.
+                    let gap = read_varint(&mut reader)?;
+                    if gap == 0 {
+                        return Err(DecodeError::NonCanonical);
+                    }
+
+                    let steps = gap.checked_add(1).ok_or(DecodeError::OutOfRange)?;
+                    previous.forward(steps).ok_or(DecodeError::OutOfRange)?
+                }
+                None => T::from_bits(read_varint(&mut reader)?).ok_or(DecodeError::OutOfRange)?,
+            };
+            let end = start
+                .forward(read_varint(&mut reader)?)
+                .ok_or(DecodeError::OutOfRange)?;
+
+            set.extend(Some(start..=end.clone()));
+            previous = Some(end);
+        }
+
+        let checksum = reader.checksum();
+        let mut stored = [0; 4];
+        read_exact(&mut reader.inner, &mut stored)?;
+        if u32::from_le_bytes(stored) != checksum {
+            return Err(DecodeError::ChecksumMismatch);
+        }
+
+        Ok(set)
+    }
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u128) -> io::Result<()> {
+    let mut bytes = [0; MAX_VARINT_LEN];
+    let mut len = 0;
+
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            bytes[len] = byte;
+            len += 1;
+            break;
+        }
+
+        bytes[len] = byte | 0x80;
+        len += 1;
+    }
+
+    writer.write_all(&bytes[..len])
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> Result<u128, DecodeError> {
+    let mut value = 0u128;
+
+    for index in 0..MAX_VARINT_LEN {
+        let mut byte = [0];
+        read_exact(reader, &mut byte)?;
+        let byte = byte[0];
+
+        let shift = 7 * index as u32;
+        let bits = (byte & 0x7f) as u128;
+        if bits << shift >> shift != bits {
+            return Err(DecodeError::OutOfRange);
+        }
+        value |= bits << shift;
+
+        if byte & 0x80 == 0 {
+            // A final byte of zero only pads the number out, so the
+            // same number could have been written shorter.
+            if byte == 0 && index > 0 {
+                return Err(DecodeError::NonCanonical);
+            }
+
+            return Ok(value);
+        }
+    }
+
+    Err(DecodeError::OutOfRange)
+}
+
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<(), DecodeError> {
+    reader.read_exact(buf).map_err(|error| match error.kind() {
+        io::ErrorKind::UnexpectedEof => DecodeError::Truncated,
+        _ => DecodeError::Io(error),
+    })
+}
+
+/// Wraps a reader or writer, keeping a CRC-32 of the bytes passing
+/// through it
+struct Checksummed<I> {
+    inner: I,
+    crc: u32,
+}
+
+impl<I> Checksummed<I> {
+    fn new(inner: I) -> Self {
+        Checksummed {
+            inner,
+            crc: 0xffff_ffff,
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            let index = (self.crc ^ byte as u32) & 0xff;
+            self.crc = CRC_TABLE[index as usize] ^ (self.crc >> 8);
+        }
+    }
+
+    fn checksum(&self) -> u32 {
+        !self.crc
+    }
+}
+
+impl<R: Read> Read for Checksummed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.update(&buf[..len]);
+
+        Ok(len)
+    }
+}
+
+impl<W: Write> Write for Checksummed<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.update(&buf[..len]);
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// The lookup table for the CRC-32 used by zlib, PNG and Ethernet
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = index as u32;
+
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[index] = crc;
+        index += 1;
+    }
+
+    table
+}
+
+/// An error returned when reading an encoded [`RangedSet`] fails
+///
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The reader failed for a reason other than running out of input
+    Io(io::Error),
+    /// The input ended before the whole set was read
+    Truncated,
+    /// The input doesn't start like an encoded set
+    BadMagic,
+    /// The input was written by a newer version of the format
+    UnsupportedVersion(u8),
+    /// The input holds a different type of value than the one being
+    /// read
+    WrongType,
+    /// A value doesn't fit in the type being read
+    OutOfRange,
+    /// The input could have been written shorter, like a padded
+    /// integer or two ranges that should have been merged
+    NonCanonical,
+    /// The checksum at the end doesn't match the input
+    ChecksumMismatch,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Io(error) => write!(f, "failed to read: {}", error),
+            DecodeError::Truncated => f.write_str("input ended early"),
+            DecodeError::BadMagic => f.write_str("not an encoded set"),
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version {}", version)
+            }
+            DecodeError::WrongType => f.write_str("encoded for a different type of value"),
+            DecodeError::OutOfRange => f.write_str("value out of range"),
+            DecodeError::NonCanonical => f.write_str("input isn't in canonical form"),
+            DecodeError::ChecksumMismatch => f.write_str("checksum mismatch"),
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::binary::{Checksummed, DecodeError};
+use crate::encodable::Encodable;
+use crate::set::RangedSet;
+
+fn encode<T: Encodable>(rs: &RangedSet<T>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    rs.encode_to(&mut bytes).unwrap();
+    bytes
+}
+
+/// Rewrites the checksum at the end of hand-edited input so only the
+/// edit itself is rejected
+fn reseal(mut bytes: Vec<u8>) -> Vec<u8> {
+    let len = bytes.len() - 4;
+    bytes.truncate(len);
+
+    let mut checksummed = Checksummed::new(());
+    checksummed.update(&bytes);
+
+    bytes.extend_from_slice(&checksummed.checksum().to_le_bytes());
+    bytes
+}
+
+#[test]
+fn encode_empty_set() {
+    let rs: RangedSet<u32> = RangedSet::new();
+    let bytes = encode(&rs);
+
+    assert_eq!(&bytes[..7], b"RSET\x01\x04\x00");
+    assert_eq!(bytes.len(), 11);
+    assert_eq!(RangedSet::decode_from(&bytes[..]).unwrap(), rs);
+}
+
+#[test]
+fn encode_layout() {
+    let rs: RangedSet<u32> = vec![3..=5, 7..=7, 200..=200].into_iter().collect();
+    let bytes = encode(&rs);
+
+    assert_eq!(
+        &bytes[..bytes.len() - 4],
+        &[b'R', b'S', b'E', b'T', 1, 4, 3, 3, 2, 1, 0, 0xc0, 0x01, 0]
+    );
+}
+
+#[test]
+fn encode_signed_layout() {
+    let rs: RangedSet<i16> = vec![-2..=1].into_iter().collect();
+    let bytes = encode(&rs);
+
+    assert_eq!(&bytes[4..bytes.len() - 4], &[1, 0x82, 1, 3, 3]);
+}
+
+#[test]
+fn checksum_is_crc32() {
+    let mut checksummed = Checksummed::new(());
+    checksummed.update(b"123456789");
+
+    assert_eq!(checksummed.checksum(), 0xcbf4_3926);
+}
+
+#[test]
+fn round_trip_signed_values() {
+    let rs: RangedSet<i64> = vec![i64::MIN..=-1000, -3..=3, 17..=17, 1000..=i64::MAX]
+        .into_iter()
+        .collect();
+
+    assert_eq!(RangedSet::decode_from(&encode(&rs)[..]).unwrap(), rs);
+}
+
+#[test]
+fn round_trip_full_domain() {
+    let rs: RangedSet<u64> = vec![0..=u64::MAX].into_iter().collect();
+
+    assert_eq!(RangedSet::decode_from(&encode(&rs)[..]).unwrap(), rs);
+}
+
+#[test]
+fn round_trip_small_types() {
+    let rs: RangedSet<i8> = vec![-128..=-120, 0..=0, 100..=127].into_iter().collect();
+    assert_eq!(RangedSet::decode_from(&encode(&rs)[..]).unwrap(), rs);
+
+    let rs: RangedSet<u8> = vec![0..=0, 2..=2, 255..=255].into_iter().collect();
+    assert_eq!(RangedSet::decode_from(&encode(&rs)[..]).unwrap(), rs);
+}
+
+#[test]
+fn decode_leaves_rest_of_input() {
+    let rs: RangedSet<u16> = vec![1..=10].into_iter().collect();
+    let mut bytes = encode(&rs);
+    bytes.extend_from_slice(b"rest");
+
+    let mut reader = &bytes[..];
+    assert_eq!(RangedSet::decode_from(&mut reader).unwrap(), rs);
+    assert_eq!(reader, b"rest");
+}
+
+#[test]
+fn decode_every_truncation() {
+    let rs: RangedSet<i32> = vec![-500..=-400, 10..=1000].into_iter().collect();
+    let bytes = encode(&rs);
+
+    for len in 0..bytes.len() {
+        let result = RangedSet::<i32>::decode_from(&bytes[..len]);
+        assert!(matches!(result, Err(DecodeError::Truncated)), "{}", len);
+    }
+}
+
+#[test]
+fn decode_bad_header() {
+    let rs: RangedSet<u32> = vec![1..=2].into_iter().collect();
+
+    let mut bytes = encode(&rs);
+    bytes[0] = b'X';
+    let result = RangedSet::<u32>::decode_from(&bytes[..]);
+    assert!(matches!(result, Err(DecodeError::BadMagic)));
+
+    let mut bytes = encode(&rs);
+    bytes[4] = 2;
+    let result = RangedSet::<u32>::decode_from(&bytes[..]);
+    assert!(matches!(result, Err(DecodeError::UnsupportedVersion(2))));
+
+    let bytes = encode(&rs);
+    let result = RangedSet::<i32>::decode_from(&bytes[..]);
+    assert!(matches!(result, Err(DecodeError::WrongType)));
+    let result = RangedSet::<u64>::decode_from(&bytes[..]);
+    assert!(matches!(result, Err(DecodeError::WrongType)));
+}
+
+#[test]
+fn decode_corrupted_input() {
+    let rs: RangedSet<u32> = vec![1..=2, 8..=9].into_iter().collect();
+    let bytes = encode(&rs);
+
+    for index in 0..bytes.len() {
+        let mut corrupted = bytes.clone();
+        corrupted[index] ^= 0x10;
+
+        let result = RangedSet::<u32>::decode_from(&corrupted[..]);
+        assert!(result.is_err(), "{}", index);
+    }
+
+    let mut corrupted = bytes;
+    corrupted[8] = 5;
+    let result = RangedSet::<u32>::decode_from(&corrupted[..]);
+    assert!(matches!(result, Err(DecodeError::ChecksumMismatch)));
+}
+
+#[test]
+fn decode_touching_ranges() {
+    let rs: RangedSet<u32> = vec![1..=2, 4..=4].into_iter().collect();
+    let mut bytes = encode(&rs);
+    assert_eq!(bytes[9], 1);
+    bytes[9] = 0;
+
+    let result = RangedSet::<u32>::decode_from(&reseal(bytes)[..]);
+    assert!(matches!(result, Err(DecodeError::NonCanonical)));
+}
+
+#[test]
+fn decode_padded_varint() {
+    let bytes = reseal(b"RSET\x01\x04\x81\x00\x01\x00\0\0\0\0".to_vec());
+
+    let result = RangedSet::<u32>::decode_from(&bytes[..]);
+    assert!(matches!(result, Err(DecodeError::NonCanonical)));
+}
+
+#[test]
+fn decode_values_out_of_range() {
+    // A start of 256 for a `u8`
+    let bytes = reseal(b"RSET\x01\x01\x01\x80\x02\x00\0\0\0\0".to_vec());
+    let result = RangedSet::<u8>::decode_from(&bytes[..]);
+    assert!(matches!(result, Err(DecodeError::OutOfRange)));
+
+    // A range from 200 running past 255
+    let bytes = reseal(b"RSET\x01\x01\x01\xc8\x01\x64\0\0\0\0".to_vec());
+    let result = RangedSet::<u8>::decode_from(&bytes[..]);
+    assert!(matches!(result, Err(DecodeError::OutOfRange)));
+
+    // A varint longer than a `u128`
+    let mut bytes = b"RSET\x01\x08\x01".to_vec();
+    bytes.extend_from_slice(&[0xff; 19]);
+    bytes.extend_from_slice(&[0x01, 0, 0, 0, 0, 0]);
+    let result = RangedSet::<u64>::decode_from(&reseal(bytes)[..]);
+    assert!(matches!(result, Err(DecodeError::OutOfRange)));
+}
+
+#[test]
+fn decode_error_messages() {
+    assert_eq!(DecodeError::Truncated.to_string(), "input ended early");
+    assert_eq!(
+        DecodeError::UnsupportedVersion(7).to_string(),
+        "unsupported format version 7"
+    );
+}
+
+#[test]
+fn round_trip_128_bit_domains() {
+    let rs: RangedSet<u128> = vec![0..=u128::MAX].into_iter().collect();
+    assert_eq!(RangedSet::decode_from(&encode(&rs)[..]).unwrap(), rs);
+
+    let rs: RangedSet<i128> = vec![i128::MIN..=-5, 0..=0, 7..=i128::MAX]
+        .into_iter()
+        .collect();
+    assert_eq!(RangedSet::decode_from(&encode(&rs)[..]).unwrap(), rs);
+}
+
+#[test]
+fn round_trip_chars_across_surrogates() {
+    let rs: RangedSet<char> = vec!['a'..='z', '\u{d000}'..='\u{efff}', char::MAX..=char::MAX]
+        .into_iter()
+        .collect();
+    let bytes = encode(&rs);
+
+    assert_eq!(RangedSet::decode_from(&bytes[..]).unwrap(), rs);
+    let result = RangedSet::<u32>::decode_from(&bytes[..]);
+    assert!(matches!(result, Err(DecodeError::WrongType)));
+}
+
+#[test]
+fn decode_surrogate_start() {
+    // A set starting at U+D800
+    let bytes = reseal(b"RSET\x01\x44\x01\x80\xb0\x03\x00\0\0\0\0".to_vec());
+    let result = RangedSet::<char>::decode_from(&bytes[..]);
+    assert!(matches!(result, Err(DecodeError::OutOfRange)));
+}
+
+#[test]
+fn round_trip_bools() {
+    for rs in [vec![], vec![false], vec![true], vec![false, true]].iter() {
+        let rs: RangedSet<bool> = rs.iter().cloned().collect();
+        assert_eq!(RangedSet::decode_from(&encode(&rs)[..]).unwrap(), rs);
+    }
+}
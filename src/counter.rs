@@ -0,0 +1,312 @@
+#[cfg(test)]
+mod tests;
+
+use crate::discrete::Discrete;
+use crate::map::{Entries, RangedMap};
+use crate::span::Span;
+use std::iter::FusedIterator;
+use std::ops::{self, RangeBounds};
+
+/// A multiset that counts how many times each value was added
+///
+/// `RangedCounter` is a run-length encoded histogram: each contiguous
+/// range of values that share a count is stored once, along with that
+/// count. Adding or subtracting over part of a range splits it, and
+/// adjacent ranges that end up with equal counts are merged again.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::RangedCounter;
+///
+/// let mut received = RangedCounter::new();
+/// received.add_range(0..=99, 1);
+/// received.add_range(40..=49, 1);
+///
+/// assert_eq!(received.count(&10), 1);
+/// assert_eq!(received.count(&45), 2);
+/// assert_eq!(received.count(&100), 0);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RangedCounter<T: Discrete> {
+    counts: RangedMap<T, usize>,
+}
+
+impl<T: Discrete> RangedCounter<T> {
+    /// Returns a new empty counter
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedCounter;
+    /// let mut counter: RangedCounter<u32> = RangedCounter::new();
+    /// ```
+    pub fn new() -> RangedCounter<T> {
+        RangedCounter {
+            counts: RangedMap::new(),
+        }
+    }
+
+    /// Returns `true` if every value has a count of zero
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedCounter;
+    ///
+    /// let mut counter = RangedCounter::new();
+    /// assert_eq!(counter.is_empty(), true);
+    ///
+    /// counter.add(1);
+    /// assert_eq!(counter.is_empty(), false);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns the number of ranges of values with a distinct count
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedCounter;
+    ///
+    /// let mut counter = RangedCounter::new();
+    /// counter.add_range(0..=9, 1);
+    /// counter.add_range(5..=9, 1);
+    /// assert_eq!(counter.range_count(), 2);
+    /// ```
+    pub fn range_count(&self) -> usize {
+        self.counts.range_count()
+    }
+
+    /// Returns how many times a value has been added
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedCounter;
+    ///
+    /// let mut counter = RangedCounter::new();
+    /// counter.add(3);
+    /// counter.add(3);
+    ///
+    /// assert_eq!(counter.count(&3), 2);
+    /// assert_eq!(counter.count(&4), 0);
+    /// ```
+    pub fn count(&self, value: &T) -> usize {
+        self.counts.get(value).cloned().unwrap_or(0)
+    }
+
+    /// Returns an iterator over the ranges of values in the counter and
+    /// their counts
+    ///
+    /// Values with a count of zero are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedCounter;
+    ///
+    /// let mut counter = RangedCounter::new();
+    /// counter.add_range(0..=9, 1);
+    /// counter.add_range(5..=9, 2);
+    ///
+    /// let counts: Vec<_> = counter.iter().collect();
+    /// assert_eq!(counts, vec![(0..=4, 1), (5..=9, 3)]);
+    /// ```
+    pub fn iter(&self) -> Counts<'_, T> {
+        Counts {
+            entries: self.counts.iter(),
+        }
+    }
+
+    /// Adds one to the count of a value
+    ///
+    /// This is the same as calling [`add_range`] with a range only
+    /// containing the value and a count of one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the count overflows a `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedCounter;
+    ///
+    /// let mut counter = RangedCounter::new();
+    /// counter.add(7);
+    ///
+    /// assert_eq!(counter.count(&7), 1);
+    /// ```
+    ///
+    /// [`add_range`]: #method.add_range
+    pub fn add(&mut self, value: T) {
+        self.add_span(Span::new(value.clone(), value), 1);
+    }
+
+    /// Subtracts one from the count of a value
+    ///
+    /// This is the same as calling [`sub_range`] with a range only
+    /// containing the value and a count of one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedCounter;
+    ///
+    /// let mut counter = RangedCounter::new();
+    /// counter.add(7);
+    /// counter.sub(7);
+    ///
+    /// assert_eq!(counter.count(&7), 0);
+    /// ```
+    ///
+    /// [`sub_range`]: #method.sub_range
+    pub fn sub(&mut self, value: T) {
+        self.sub_span(Span::new(value.clone(), value), 1);
+    }
+
+    /// Adds to the count of every value in a range
+    ///
+    /// Ranges of values that are only partly covered by the range are
+    /// split, and ranges that end up with equal counts as their
+    /// neighbours are merged with them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a count overflows a `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedCounter;
+    ///
+    /// let mut counter = RangedCounter::new();
+    /// counter.add_range(0..10, 2);
+    /// counter.add_range(5..15, 1);
+    ///
+    /// assert_eq!(counter.count(&0), 2);
+    /// assert_eq!(counter.count(&7), 3);
+    /// assert_eq!(counter.count(&12), 1);
+    /// ```
+    pub fn add_range<R>(&mut self, range: R, count: usize)
+    where
+        R: RangeBounds<T>,
+    {
+        if let Some(range) = Span::from_bounds(&range) {
+            self.add_span(range, count);
+        }
+    }
+
+    fn add_span(&mut self, range: Span<T>, count: usize) {
+        if count == 0 {
+            return;
+        }
+
+        self.counts.update_range(range, |current| {
+            let current = current.cloned().unwrap_or(0);
+
+            Some(current.checked_add(count).expect("count overflowed"))
+        });
+    }
+
+    /// Subtracts from the count of every value in a range
+    ///
+    /// Counts never go below zero; values whose count reaches zero are
+    /// removed from the counter.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedCounter;
+    ///
+    /// let mut counter = RangedCounter::new();
+    /// counter.add_range(0..10, 2);
+    /// counter.sub_range(5..15, 1);
+    ///
+    /// assert_eq!(counter.count(&0), 2);
+    /// assert_eq!(counter.count(&7), 1);
+    /// assert_eq!(counter.count(&12), 0);
+    /// ```
+    pub fn sub_range<R>(&mut self, range: R, count: usize)
+    where
+        R: RangeBounds<T>,
+    {
+        if let Some(range) = Span::from_bounds(&range) {
+            self.sub_span(range, count);
+        }
+    }
+
+    fn sub_span(&mut self, range: Span<T>, count: usize) {
+        if count == 0 {
+            return;
+        }
+
+        self.counts.update_range(range, |current| {
+            match current.cloned().unwrap_or(0).saturating_sub(count) {
+                0 => None,
+                remaining => Some(remaining),
+            }
+        });
+    }
+
+    /// Returns the sum of the counts of every value
+    ///
+    /// The sum saturates at `u128::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedCounter;
+    ///
+    /// let mut counter = RangedCounter::new();
+    /// counter.add_range(0..10, 2);
+    /// counter.add(20);
+    ///
+    /// assert_eq!(counter.total(), 21);
+    /// ```
+    pub fn total(&self) -> u128 {
+        self.iter()
+            .map(|(range, count)| Span::from(range).len().saturating_mul(count as u128))
+            .fold(0, u128::saturating_add)
+    }
+}
+
+/// An iterator over the ranges of values in a [`RangedCounter`] and
+/// their counts
+///
+/// This `struct` is created by the [`iter`] method on
+/// [`RangedCounter`].
+///
+/// [`iter`]: struct.RangedCounter.html#method.iter
+/// [`RangedCounter`]: struct.RangedCounter.html
+#[derive(Clone, Debug)]
+pub struct Counts<'a, T: Discrete> {
+    entries: Entries<'a, T, usize>,
+}
+
+impl<'a, T: Discrete> Iterator for Counts<'a, T> {
+    type Item = (ops::RangeInclusive<T>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(range, &count)| (range, count))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a, T: Discrete> DoubleEndedIterator for Counts<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next_back()
+            .map(|(range, &count)| (range, count))
+    }
+}
+
+impl<'a, T: Discrete> ExactSizeIterator for Counts<'a, T> {}
+
+impl<'a, T: Discrete> FusedIterator for Counts<'a, T> {}
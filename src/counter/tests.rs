@@ -0,0 +1,151 @@
+use crate::counter::RangedCounter;
+
+fn counts(counter: &RangedCounter<i32>) -> Vec<(std::ops::RangeInclusive<i32>, usize)> {
+    counter.iter().collect()
+}
+
+#[test]
+fn add_range_to_empty_counter() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(2..=5, 1);
+
+    assert_eq!(counts(&counter), vec![(2..=5, 1)]);
+}
+
+#[test]
+fn add_range_with_zero_count_or_empty_range() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(2..=5, 0);
+    counter.add_range(3..3, 1);
+
+    assert!(counter.is_empty());
+}
+
+#[test]
+fn add_range_splits_where_counts_differ() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(0..=9, 1);
+    counter.add_range(3..=5, 1);
+
+    assert_eq!(counts(&counter), vec![(0..=2, 1), (3..=5, 2), (6..=9, 1)]);
+}
+
+#[test]
+fn add_range_spanning_gaps() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(2..=3, 1);
+    counter.add_range(6..=7, 2);
+    counter.add_range(0..=9, 1);
+
+    assert_eq!(
+        counts(&counter),
+        vec![(0..=1, 1), (2..=3, 2), (4..=5, 1), (6..=7, 3), (8..=9, 1)]
+    );
+}
+
+#[test]
+fn add_merges_equal_neighbours() {
+    let mut counter = RangedCounter::new();
+    counter.add(1);
+    counter.add(3);
+    counter.add(2);
+
+    assert_eq!(counts(&counter), vec![(1..=3, 1)]);
+    assert_eq!(counter.range_count(), 1);
+}
+
+#[test]
+fn add_range_evens_out_counts() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(0..=9, 1);
+    counter.add_range(0..=4, 1);
+    counter.add_range(5..=9, 1);
+
+    assert_eq!(counts(&counter), vec![(0..=9, 2)]);
+}
+
+#[test]
+fn sub_range_removes_values_reaching_zero() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(0..=9, 1);
+    counter.add_range(3..=5, 1);
+    counter.sub_range(2..=6, 1);
+
+    assert_eq!(counts(&counter), vec![(0..=1, 1), (3..=5, 1), (7..=9, 1)]);
+}
+
+#[test]
+fn sub_range_saturates_at_zero() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(0..=9, 2);
+    counter.sub_range(0..=4, 5);
+    counter.sub_range(20..=30, 1);
+
+    assert_eq!(counts(&counter), vec![(5..=9, 2)]);
+}
+
+#[test]
+fn sub_undoes_add() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(0..=9, 1);
+    counter.add(4);
+    counter.sub(4);
+
+    assert_eq!(counts(&counter), vec![(0..=9, 1)]);
+}
+
+#[test]
+fn count_of_values() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(0..=9, 1);
+    counter.add_range(5..=14, 2);
+
+    assert_eq!(counter.count(&-1), 0);
+    assert_eq!(counter.count(&0), 1);
+    assert_eq!(counter.count(&5), 3);
+    assert_eq!(counter.count(&14), 2);
+    assert_eq!(counter.count(&15), 0);
+}
+
+#[test]
+fn total_sums_counts() {
+    let mut counter = RangedCounter::new();
+    assert_eq!(counter.total(), 0);
+
+    counter.add_range(0..=9, 1);
+    counter.add_range(5..=14, 2);
+    assert_eq!(counter.total(), 30);
+}
+
+#[test]
+#[should_panic(expected = "count overflowed")]
+fn add_range_overflowing_count() {
+    let mut counter = RangedCounter::new();
+    counter.add(1u8);
+    counter.add_range(0..=2u8, usize::MAX);
+}
+
+#[test]
+fn counts_in_both_directions() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(0..=9, 1);
+    counter.add_range(3..=5, 1);
+
+    let mut iter = counter.iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some((6..=9, 1)));
+    assert_eq!(iter.next(), Some((0..=2, 1)));
+    assert_eq!(iter.next(), Some((3..=5, 2)));
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn unbounded_ranges() {
+    let mut counter = RangedCounter::new();
+    counter.add_range(.., 1);
+    counter.add_range(..10u8, 1);
+    counter.sub_range(250.., 1);
+
+    let counts: Vec<_> = counter.iter().collect();
+    assert_eq!(counts, vec![(0..=9, 2), (10..=249, 1)]);
+}
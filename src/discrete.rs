@@ -0,0 +1,240 @@
+#[cfg(test)]
+mod tests;
+
+use std::convert::TryFrom;
+
+/// Types whose values can be counted off one after another
+///
+/// `Discrete` is everything a [`RangedSet`] needs to know about its
+/// values: which value comes right after or before another, how many
+/// steps apart two values are, and the smallest and largest values of
+/// the type. It's implemented for every primitive integer type, `char`
+/// and `bool`, and [`discrete_newtype!`] implements it for types that
+/// wrap one of them.
+///
+/// Distances are measured in `u128` so ranges covering the whole domain
+/// of any primitive type can be measured. The one thing a `u128` can't
+/// count is every value of a `u128` or `i128`, so lengths saturate at
+/// `u128::MAX`.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::Discrete;
+///
+/// assert_eq!(5u8.successor(), Some(6));
+/// assert_eq!(255u8.successor(), None);
+/// assert_eq!(0u8.distance(&255), 255);
+/// assert_eq!(250u8.forward(6), None);
+///
+/// // Surrogate code points aren't `char`s, so they're stepped over
+/// assert_eq!('\u{d7ff}'.successor(), Some('\u{e000}'));
+///
+/// assert_eq!(<i8 as Discrete>::MIN, -128);
+/// ```
+///
+/// [`RangedSet`]: struct.RangedSet.html
+/// [`discrete_newtype!`]: macro.discrete_newtype.html
+pub trait Discrete: Clone + Ord {
+    /// The smallest value of the type
+    const MIN: Self;
+    /// The largest value of the type
+    const MAX: Self;
+
+    /// Returns the value right after this one, or `None` if this is the
+    /// largest value
+    fn successor(&self) -> Option<Self>;
+
+    /// Returns the value right before this one, or `None` if this is the
+    /// smallest value
+    fn predecessor(&self) -> Option<Self>;
+
+    /// Returns the number of steps between the value and another
+    ///
+    /// The order of the values doesn't matter and the result is always
+    /// positive.
+    fn distance(&self, other: &Self) -> u128;
+
+    /// Returns the value a number of steps after this one, or `None` if
+    /// the result is past the largest value
+    fn forward(&self, steps: u128) -> Option<Self>;
+}
+
+macro_rules! integer_impl {
+    ($t:ty, $unsigned:ty) => {
+        impl Discrete for $t {
+            const MAX: Self = <$t>::MAX;
+            const MIN: Self = <$t>::MIN;
+
+            fn successor(&self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn predecessor(&self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn distance(&self, other: &Self) -> u128 {
+                let (low, high) = if self < other {
+                    (self, other)
+                } else {
+                    (other, self)
+                };
+
+                // The difference overflows signed types but always fits
+                // in the unsigned type of the same width.
+                high.wrapping_sub(*low) as $unsigned as u128
+            }
+
+            fn forward(&self, steps: u128) -> Option<Self> {
+                if steps > self.distance(&<$t>::MAX) {
+                    return None;
+                }
+
+                Some((*self as u128).wrapping_add(steps) as $t)
+            }
+        }
+    };
+}
+
+integer_impl!(i8, u8);
+integer_impl!(i16, u16);
+integer_impl!(i32, u32);
+integer_impl!(i64, u64);
+integer_impl!(i128, u128);
+integer_impl!(isize, usize);
+integer_impl!(u8, u8);
+integer_impl!(u16, u16);
+integer_impl!(u32, u32);
+integer_impl!(u64, u64);
+integer_impl!(u128, u128);
+integer_impl!(usize, usize);
+
+/// The number of surrogate code points, which sit between the `char`s
+/// `'\u{d7ff}'` and `'\u{e000}'` but aren't `char`s themselves
+const SURROGATES: u32 = 0x800;
+
+/// Returns the position of a `char` among all `char`s
+fn char_index(c: char) -> u32 {
+    match c as u32 {
+        c if c >= 0xe000 => c - SURROGATES,
+        c => c,
+    }
+}
+
+fn char_at(index: u32) -> Option<char> {
+    let c = if index >= 0xd800 {
+        index.checked_add(SURROGATES)?
+    } else {
+        index
+    };
+
+    char::from_u32(c)
+}
+
+impl Discrete for char {
+    const MAX: Self = char::MAX;
+    const MIN: Self = '\0';
+
+    fn successor(&self) -> Option<Self> {
+        char_at(char_index(*self) + 1)
+    }
+
+    fn predecessor(&self) -> Option<Self> {
+        char_at(char_index(*self).checked_sub(1)?)
+    }
+
+    fn distance(&self, other: &Self) -> u128 {
+        char_index(*self).distance(&char_index(*other))
+    }
+
+    fn forward(&self, steps: u128) -> Option<Self> {
+        let steps = u32::try_from(steps).ok()?;
+
+        char_at(char_index(*self).checked_add(steps)?)
+    }
+}
+
+impl Discrete for bool {
+    const MAX: Self = true;
+    const MIN: Self = false;
+
+    fn successor(&self) -> Option<Self> {
+        if *self { None } else { Some(true) }
+    }
+
+    fn predecessor(&self) -> Option<Self> {
+        if *self { Some(false) } else { None }
+    }
+
+    fn distance(&self, other: &Self) -> u128 {
+        (self != other) as u128
+    }
+
+    fn forward(&self, steps: u128) -> Option<Self> {
+        match steps {
+            0 => Some(*self),
+            1 => self.successor(),
+            _ => None,
+        }
+    }
+}
+
+/// Implements [`Discrete`] for newtypes around discrete types
+///
+/// Each newtype has to be a tuple struct with a single field of a type
+/// that implements `Discrete`. It has to implement `Clone` and `Ord`
+/// itself, ordering the same way its field does, which deriving them
+/// takes care of.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::{RangedSet, discrete_newtype};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// struct UserId(u64);
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Port(u16);
+///
+/// discrete_newtype!(UserId(u64), Port(u16));
+///
+/// let mut ids = RangedSet::new();
+/// ids.insert_range(UserId(1)..=UserId(5));
+/// ids.insert(UserId(6));
+/// assert_eq!(ids.range_count(), 1);
+///
+/// let mut privileged = RangedSet::new();
+/// privileged.insert_range(Port(0)..Port(1024));
+/// assert_eq!(privileged.complement().first(), Some(&Port(1024)));
+/// ```
+///
+/// [`Discrete`]: trait.Discrete.html
+#[macro_export]
+macro_rules! discrete_newtype {
+    ($($name:ident($inner:ty)),+ $(,)?) => {
+        $(
+            impl $crate::Discrete for $name {
+                const MIN: Self = $name(<$inner as $crate::Discrete>::MIN);
+                const MAX: Self = $name(<$inner as $crate::Discrete>::MAX);
+
+                fn successor(&self) -> Option<Self> {
+                    $crate::Discrete::successor(&self.0).map($name)
+                }
+
+                fn predecessor(&self) -> Option<Self> {
+                    $crate::Discrete::predecessor(&self.0).map($name)
+                }
+
+                fn distance(&self, other: &Self) -> u128 {
+                    $crate::Discrete::distance(&self.0, &other.0)
+                }
+
+                fn forward(&self, steps: u128) -> Option<Self> {
+                    $crate::Discrete::forward(&self.0, steps).map($name)
+                }
+            }
+        )+
+    };
+}
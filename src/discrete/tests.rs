@@ -0,0 +1,117 @@
+use crate::discrete::Discrete;
+use crate::discrete_newtype;
+use crate::set::RangedSet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Id(u32);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Offset(i8);
+
+discrete_newtype!(Id(u32), Offset(i8),);
+
+#[test]
+fn integer_steps_stop_at_bounds() {
+    assert_eq!(0u8.predecessor(), None);
+    assert_eq!(255u8.successor(), None);
+    assert_eq!((-128i8).predecessor(), None);
+    assert_eq!(127i8.successor(), None);
+    assert_eq!(u128::MAX.successor(), None);
+    assert_eq!(i128::MIN.predecessor(), None);
+    assert_eq!((-1i128).successor(), Some(0));
+}
+
+#[test]
+fn integer_distance_covers_whole_domain() {
+    assert_eq!(i8::MIN.distance(&i8::MAX), 255);
+    assert_eq!(i64::MAX.distance(&i64::MIN), u64::MAX as u128);
+    assert_eq!(i128::MIN.distance(&i128::MAX), u128::MAX);
+    assert_eq!(0u128.distance(&u128::MAX), u128::MAX);
+    assert_eq!(7isize.distance(&7), 0);
+}
+
+#[test]
+fn integer_forward() {
+    assert_eq!((-100i8).forward(227), Some(127));
+    assert_eq!((-100i8).forward(228), None);
+    assert_eq!(i128::MIN.forward(u128::MAX), Some(i128::MAX));
+    assert_eq!(1u128.forward(u128::MAX), None);
+    assert_eq!(5u16.forward(0), Some(5));
+}
+
+#[test]
+fn char_steps_over_surrogates() {
+    assert_eq!('\u{d7ff}'.successor(), Some('\u{e000}'));
+    assert_eq!('\u{e000}'.predecessor(), Some('\u{d7ff}'));
+    assert_eq!('\u{d7ff}'.distance(&'\u{e000}'), 1);
+    assert_eq!('\u{d7fe}'.forward(3), Some('\u{e001}'));
+
+    assert_eq!('\0'.predecessor(), None);
+    assert_eq!(char::MAX.successor(), None);
+    assert_eq!('\0'.distance(&char::MAX), 0x10_ffff - 0x800);
+    assert_eq!('\0'.forward(0x10_ffff - 0x800), Some(char::MAX));
+    assert_eq!('\0'.forward(0x11_0000), None);
+}
+
+#[test]
+fn char_set_spans_surrogates() {
+    let mut rs = RangedSet::new();
+    rs.insert('\u{d7ff}');
+    rs.insert('\u{e000}');
+
+    assert_eq!(rs.range_count(), 1);
+    assert_eq!(rs.len(), 2);
+
+    let all: RangedSet<char> = RangedSet::new().complement();
+    assert_eq!(all.len(), 0x11_0000 - 0x800);
+}
+
+#[test]
+fn bool_steps() {
+    assert_eq!(false.successor(), Some(true));
+    assert_eq!(true.successor(), None);
+    assert_eq!(true.predecessor(), Some(false));
+    assert_eq!(false.predecessor(), None);
+    assert_eq!(false.distance(&true), 1);
+    assert_eq!(false.forward(1), Some(true));
+    assert_eq!(false.forward(2), None);
+
+    let mut rs = RangedSet::new();
+    rs.insert(true);
+    assert_eq!(rs.complement().iter().collect::<Vec<_>>(), vec![false]);
+
+    rs.insert(false);
+    assert_eq!(rs.range_count(), 1);
+    assert_eq!(rs.len(), 2);
+}
+
+#[test]
+fn full_128_bit_domains_saturate_len() {
+    let mut rs = RangedSet::new();
+    rs.insert_range(..);
+    assert_eq!(rs.len(), u128::MAX);
+    assert!(rs.contains(&u128::MAX));
+
+    let mut rs: RangedSet<i128> = RangedSet::new();
+    rs.insert_range(i128::MIN..=-1);
+    rs.insert_range(1..);
+    assert_eq!(rs.len(), u128::MAX);
+    assert_eq!(rs.complement().iter().collect::<Vec<_>>(), vec![0]);
+}
+
+#[test]
+fn newtypes_delegate_to_field() {
+    assert_eq!(Id(4).successor(), Some(Id(5)));
+    assert_eq!(Id::MAX.successor(), None);
+    assert_eq!(Offset::MIN, Offset(-128));
+    assert_eq!(Offset(-1).distance(&Offset(1)), 2);
+    assert_eq!(Offset(120).forward(8), None);
+
+    let mut rs = RangedSet::new();
+    rs.insert_range(Id(10)..Id(20));
+    rs.insert(Id(20));
+    rs.insert(Id(9));
+
+    assert_eq!(rs.ranges().collect::<Vec<_>>(), vec![Id(9)..=Id(20)]);
+    assert_eq!(rs.len(), 12);
+}
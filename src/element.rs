@@ -1,31 +1,72 @@
 #[cfg(test)]
 mod tests;
 
-use crate::range_inclusive::RangeInclusive;
+use crate::discrete::Discrete;
+use crate::span::Span;
 use std::cmp::Ordering;
-use step::Step;
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum Element<T: Step + Clone + Ord> {
+/// The reasons an element can't be split around a value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitError {
+    /// The element only holds one value, so there's nothing to split
+    Single,
+    /// The value isn't inside the element
+    OutOfRange,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Element<T: Discrete> {
     Single(T),
-    Range(RangeInclusive<T>),
+    Range(Span<T>),
 }
 
-impl<T: Step + Clone + Ord> From<T> for Element<T> {
+impl<T: Discrete> From<T> for Element<T> {
     fn from(v: T) -> Self {
         Element::Single(v)
     }
 }
 
-impl<T: Step + Clone + Ord> From<RangeInclusive<T>> for Element<T> {
-    fn from(v: RangeInclusive<T>) -> Self {
+impl<T: Discrete> From<Span<T>> for Element<T> {
+    fn from(v: Span<T>) -> Self {
         Element::Range(v)
     }
 }
 
-impl<T: Step + Clone + Ord> Element<T> {
+impl<T: Discrete> Element<T> {
+    pub fn from_bounds(start: T, end: T) -> Self {
+        if start == end {
+            Element::Single(start)
+        } else {
+            Element::Range(Span::new(start, end))
+        }
+    }
+
+    pub fn start(&self) -> &T {
+        match self {
+            Element::Single(ref s) => s,
+            Element::Range(ref r) => &r.start,
+        }
+    }
+
+    pub fn end(&self) -> &T {
+        match self {
+            Element::Single(ref s) => s,
+            Element::Range(ref r) => &r.end,
+        }
+    }
+
+    pub fn cmp_value(&self, value: &T) -> Ordering {
+        if self.end() < value {
+            Ordering::Less
+        } else if value < self.start() {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
     pub fn adjacent_to(&self, value: &T) -> bool {
-        match (self.prev(), self.next()) {
+        match (self.predecessor(), self.successor()) {
             (Some(ref p), Some(ref n)) => value == p || value == n,
             (Some(ref p), None) => value == p,
             (None, Some(ref n)) => value == n,
@@ -33,126 +74,91 @@ impl<T: Step + Clone + Ord> Element<T> {
         }
     }
 
-    pub fn merge<S>(self, value: S) -> Self
+    pub fn merge<S>(self, value: S) -> Option<Self>
     where
         S: Into<Self>,
     {
-        use crate::element::Element::{Range, Single};
-        let v = value.into();
-
-        match (self, v) {
-            (Single(s), Single(v)) => match (s.prev(), s.next()) {
-                (Some(p), Some(n)) => match (v == p, v == n) {
-                    (true, false) => Range(RangeInclusive::new(v, s)),
-                    (false, true) => Range(RangeInclusive::new(s, v)),
-                    (false, false) => unimplemented!(),
-                    (true, true) => unreachable!(),
-                },
-                (Some(p), None) if v == p => Range(RangeInclusive::new(v, s)),
-                (None, Some(n)) if v == n => Range(RangeInclusive::new(s, v)),
-                _ => unimplemented!(),
-            },
-            (Single(s), Range(v)) => match (s.prev(), s.next()) {
-                (Some(p), Some(n)) => match (v.end == p, v.start == n) {
-                    (true, false) => Range(RangeInclusive::new(v.start, s)),
-                    (false, true) => Range(RangeInclusive::new(s, v.end)),
-                    (false, false) => unimplemented!(),
-                    (true, true) => unreachable!(),
-                },
-                (Some(p), None) if v.end == p => Range(RangeInclusive::new(v.start, s)),
-                (None, Some(n)) if v.start == n => Range(RangeInclusive::new(s, v.end)),
-                _ => unimplemented!(),
-            },
-            (Range(r), Single(v)) => match (r.start.prev(), r.end.next()) {
-                (Some(p), Some(n)) => match (v == p, v == n) {
-                    (true, false) => Range(RangeInclusive::new(v, r.end)),
-                    (false, true) => Range(RangeInclusive::new(r.start, v)),
-                    (false, false) => unimplemented!(),
-                    (true, true) => unreachable!(),
-                },
-                (Some(p), None) if v == p => Range(RangeInclusive::new(v, r.end)),
-                (None, Some(n)) if v == n => Range(RangeInclusive::new(r.start, v)),
-                _ => unimplemented!(),
-            },
-            (Range(r), Range(v)) => match (r.start.prev(), r.end.next()) {
-                (Some(p), Some(n)) => match (v.end == p, v.start == n) {
-                    (true, false) => Range(RangeInclusive::new(v.start, r.end)),
-                    (false, true) => Range(RangeInclusive::new(r.start, v.end)),
-                    (false, false) => unimplemented!(),
-                    (true, true) => unreachable!(),
-                },
-                (Some(p), None) if v.end == p => Range(RangeInclusive::new(v.start, r.end)),
-                (None, Some(n)) if v.start == n => Range(RangeInclusive::new(r.start, v.end)),
-                _ => unimplemented!(),
-            },
+        let other = value.into();
+
+        // Order the two elements by where they start so that only the
+        // end of the first one needs checking against the second.
+        let (first, second) = if self.start() <= other.start() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let touching =
+            first.end() >= second.start() || first.successor().as_ref() == Some(second.start());
+        if !touching {
+            return None;
         }
+
+        let end = if first.end() < second.end() {
+            second.end().clone()
+        } else {
+            first.end().clone()
+        };
+
+        Some(Element::from_bounds(first.start().clone(), end))
     }
 
-    pub fn split(&self, value: &T) -> (Option<Self>, T, Option<Self>) {
+    pub fn split(&self, value: &T) -> Result<(Option<Self>, T, Option<Self>), SplitError> {
         match self {
-            Element::Range(ref r) => match (value.prev(), value.next()) {
-                (Some(p), Some(n)) => {
-                    let prev = match r.start.cmp(&p) {
-                        Ordering::Equal => Some(Element::Single(p)),
-                        Ordering::Greater => None,
-                        Ordering::Less => {
-                            Some(Element::Range(RangeInclusive::new(r.start.clone(), p)))
-                        }
-                    };
-
-                    let next = match r.end.cmp(&n) {
-                        Ordering::Equal => Some(Element::Single(n)),
-                        Ordering::Less => None,
-                        Ordering::Greater => {
-                            Some(Element::Range(RangeInclusive::new(n, r.end.clone())))
-                        }
-                    };
-
-                    (prev, value.clone(), next)
-                }
-                (Some(p), None) => {
-                    let prev = match r.start.cmp(&p) {
-                        Ordering::Equal => Some(Element::Single(p)),
-                        Ordering::Greater => None,
-                        Ordering::Less => {
-                            Some(Element::Range(RangeInclusive::new(r.start.clone(), p)))
-                        }
-                    };
-
-                    let next = None;
-
-                    (prev, value.clone(), next)
-                }
-                (None, Some(n)) => {
-                    let prev = None;
-
-                    let next = match r.end.cmp(&n) {
-                        Ordering::Equal => Some(Element::Single(n)),
-                        Ordering::Less => None,
-                        Ordering::Greater => {
-                            Some(Element::Range(RangeInclusive::new(n, r.end.clone())))
-                        }
-                    };
-
-                    (prev, value.clone(), next)
-                }
-                _ => unreachable!(),
-            },
-            Element::Single(_) => unreachable!(),
+            Element::Range(_) => {
+                let range = Span::new(value.clone(), value.clone());
+                let (prev, _, next) = self.split_range(&range).ok_or(SplitError::OutOfRange)?;
+
+                Ok((prev, value.clone(), next))
+            }
+            Element::Single(_) => Err(SplitError::Single),
         }
     }
 
-    fn next(&self) -> Option<T> {
+    pub fn split_range(&self, range: &Span<T>) -> Option<(Option<Self>, Self, Option<Self>)> {
+        let disjoint = self.cmp_value(&range.start) == Ordering::Less
+            || self.cmp_value(&range.end) == Ordering::Greater;
+        if range.end < range.start || disjoint {
+            return None;
+        }
+
+        let prev = match self.start().cmp(&range.start) {
+            Ordering::Less => range
+                .start
+                .predecessor()
+                .map(|p| Element::from_bounds(self.start().clone(), p)),
+            _ => None,
+        };
+
+        let next = match self.end().cmp(&range.end) {
+            Ordering::Greater => range
+                .end
+                .successor()
+                .map(|n| Element::from_bounds(n, self.end().clone())),
+            _ => None,
+        };
+
+        let start = std::cmp::max(self.start(), &range.start).clone();
+        let end = std::cmp::min(self.end(), &range.end).clone();
+
+        Some((prev, Element::from_bounds(start, end), next))
+    }
+
+    fn successor(&self) -> Option<T> {
         match self {
-            Element::Single(ref s) => s.next(),
-            Element::Range(ref r) => r.end.next(),
+            Element::Single(ref s) => s.successor(),
+            Element::Range(ref r) => r.end.successor(),
         }
     }
 
-    fn prev(&self) -> Option<T> {
+    fn predecessor(&self) -> Option<T> {
         match self {
-            Element::Single(ref s) => s.prev(),
-            Element::Range(ref r) => r.start.prev(),
+            Element::Single(ref s) => s.predecessor(),
+            Element::Range(ref r) => r.start.predecessor(),
         }
     }
+
+    pub fn len(&self) -> u128 {
+        self.start().distance(self.end()).saturating_add(1)
+    }
 }
@@ -1,130 +1,237 @@
 use crate::element::Element::{Range, Single};
-use crate::range_inclusive::RangeInclusive;
+use crate::element::SplitError;
+use crate::span::Span;
 
 #[test]
 fn merge_number_with_single_element() {
-    assert_eq!(Single(0u8).merge(1), Range(RangeInclusive::new(0, 1)));
-    assert_eq!(Single(1u8).merge(0), Range(RangeInclusive::new(0, 1)));
-    assert_eq!(Single(10u8).merge(9), Range(RangeInclusive::new(9, 10)));
-    assert_eq!(Single(10u8).merge(11), Range(RangeInclusive::new(10, 11)));
-    assert_eq!(
-        Single(255u8).merge(254),
-        Range(RangeInclusive::new(254, 255))
-    );
-    assert_eq!(
-        Single(254u8).merge(255),
-        Range(RangeInclusive::new(254, 255))
-    );
+    assert_eq!(Single(0u8).merge(1), Some(Range(Span::new(0, 1))));
+    assert_eq!(Single(1u8).merge(0), Some(Range(Span::new(0, 1))));
+    assert_eq!(Single(10u8).merge(9), Some(Range(Span::new(9, 10))));
+    assert_eq!(Single(10u8).merge(11), Some(Range(Span::new(10, 11))));
+    assert_eq!(Single(255u8).merge(254), Some(Range(Span::new(254, 255))));
+    assert_eq!(Single(254u8).merge(255), Some(Range(Span::new(254, 255))));
 }
 
 #[test]
 fn merge_number_with_range_element() {
     assert_eq!(
-        Range(RangeInclusive::new(0, 1)).merge(2),
-        Range(RangeInclusive::new(0, 2))
+        Range(Span::new(0, 1)).merge(2),
+        Some(Range(Span::new(0, 2)))
     );
     assert_eq!(
-        Range(RangeInclusive::new(10, 11)).merge(9),
-        Range(RangeInclusive::new(9, 11))
+        Range(Span::new(10, 11)).merge(9),
+        Some(Range(Span::new(9, 11)))
     );
     assert_eq!(
-        Range(RangeInclusive::new(10, 11)).merge(12),
-        Range(RangeInclusive::new(10, 12))
+        Range(Span::new(10, 11)).merge(12),
+        Some(Range(Span::new(10, 12)))
     );
 }
 
 #[test]
 fn merge_range_with_single_element() {
     assert_eq!(
-        Single(0u8).merge(RangeInclusive::new(1, 2)),
-        Range(RangeInclusive::new(0, 2))
+        Single(0u8).merge(Span::new(1, 2)),
+        Some(Range(Span::new(0, 2)))
     );
     assert_eq!(
-        Single(2u8).merge(RangeInclusive::new(0, 1)),
-        Range(RangeInclusive::new(0, 2))
+        Single(2u8).merge(Span::new(0, 1)),
+        Some(Range(Span::new(0, 2)))
     );
     assert_eq!(
-        Single(10u8).merge(RangeInclusive::new(8, 9)),
-        Range(RangeInclusive::new(8, 10))
+        Single(10u8).merge(Span::new(8, 9)),
+        Some(Range(Span::new(8, 10)))
     );
     assert_eq!(
-        Single(10u8).merge(RangeInclusive::new(11, 12)),
-        Range(RangeInclusive::new(10, 12))
+        Single(10u8).merge(Span::new(11, 12)),
+        Some(Range(Span::new(10, 12)))
     );
     assert_eq!(
-        Single(253u8).merge(RangeInclusive::new(254, 255)),
-        Range(RangeInclusive::new(253, 255))
+        Single(253u8).merge(Span::new(254, 255)),
+        Some(Range(Span::new(253, 255)))
     );
     assert_eq!(
-        Single(255u8).merge(RangeInclusive::new(253, 254)),
-        Range(RangeInclusive::new(253, 255))
+        Single(255u8).merge(Span::new(253, 254)),
+        Some(Range(Span::new(253, 255)))
     );
 }
 
 #[test]
 fn merge_range_with_range_element() {
     assert_eq!(
-        Range(RangeInclusive::new(0, 1)).merge(RangeInclusive::new(2, 3)),
-        Range(RangeInclusive::new(0, 3))
+        Range(Span::new(0, 1)).merge(Span::new(2, 3)),
+        Some(Range(Span::new(0, 3)))
     );
     assert_eq!(
-        Range(RangeInclusive::new(10, 11)).merge(RangeInclusive::new(8, 9)),
-        Range(RangeInclusive::new(8, 11))
+        Range(Span::new(10, 11)).merge(Span::new(8, 9)),
+        Some(Range(Span::new(8, 11)))
     );
     assert_eq!(
-        Range(RangeInclusive::new(10, 11)).merge(RangeInclusive::new(12, 13)),
-        Range(RangeInclusive::new(10, 13))
+        Range(Span::new(10, 11)).merge(Span::new(12, 13)),
+        Some(Range(Span::new(10, 13)))
     );
     assert_eq!(
-        Range(RangeInclusive::new(254, 255)).merge(RangeInclusive::new(252, 253)),
-        Range(RangeInclusive::new(252, 255))
+        Range(Span::new(254, 255)).merge(Span::new(252, 253)),
+        Some(Range(Span::new(252, 255)))
     );
 }
 
 #[test]
 fn split_range_with_two_elements() {
     assert_eq!(
-        Range(RangeInclusive::new(0, 1)).split(&0),
-        (None, 0, Some(Single(1)))
+        Range(Span::new(0, 1)).split(&0),
+        Ok((None, 0, Some(Single(1))))
     );
     assert_eq!(
-        Range(RangeInclusive::new(0, 1)).split(&1),
-        (Some(Single(0)), 1, None)
+        Range(Span::new(0, 1)).split(&1),
+        Ok((Some(Single(0)), 1, None))
     );
 }
 
 #[test]
 fn split_range_with_three_elements() {
     assert_eq!(
-        Range(RangeInclusive::new(0, 2)).split(&0),
-        (None, 0, Some(Range(RangeInclusive::new(1, 2))))
+        Range(Span::new(0, 2)).split(&0),
+        Ok((None, 0, Some(Range(Span::new(1, 2)))))
     );
     assert_eq!(
-        Range(RangeInclusive::new(0, 2)).split(&1),
-        (Some(Single(0)), 1, Some(Single(2)))
+        Range(Span::new(0, 2)).split(&1),
+        Ok((Some(Single(0)), 1, Some(Single(2))))
     );
     assert_eq!(
-        Range(RangeInclusive::new(0, 2)).split(&2),
-        (Some(Range(RangeInclusive::new(0, 1))), 2, None)
+        Range(Span::new(0, 2)).split(&2),
+        Ok((Some(Range(Span::new(0, 1))), 2, None))
     );
 }
 
 #[test]
 fn split_range_with_more_elements() {
     assert_eq!(
-        Range(RangeInclusive::new(0, 3)).split(&0),
-        (None, 0, Some(Range(RangeInclusive::new(1, 3))))
+        Range(Span::new(0, 3)).split(&0),
+        Ok((None, 0, Some(Range(Span::new(1, 3)))))
+    );
+    assert_eq!(
+        Range(Span::new(0, 3)).split(&1),
+        Ok((Some(Single(0)), 1, Some(Range(Span::new(2, 3)))))
+    );
+    assert_eq!(
+        Range(Span::new(0, 3)).split(&2),
+        Ok((Some(Range(Span::new(0, 1))), 2, Some(Single(3))))
+    );
+    assert_eq!(
+        Range(Span::new(0, 3)).split(&3),
+        Ok((Some(Range(Span::new(0, 2))), 3, None))
+    );
+}
+
+#[test]
+fn merge_overlapping_elements() {
+    assert_eq!(Single(3u8).merge(3), Some(Single(3)));
+    assert_eq!(
+        Range(Span::new(0u8, 5)).merge(3),
+        Some(Range(Span::new(0, 5)))
+    );
+    assert_eq!(
+        Single(3u8).merge(Span::new(0, 5)),
+        Some(Range(Span::new(0, 5)))
+    );
+    assert_eq!(
+        Range(Span::new(0u8, 5)).merge(Span::new(3, 8)),
+        Some(Range(Span::new(0, 8)))
+    );
+    assert_eq!(
+        Range(Span::new(3u8, 8)).merge(Span::new(0, 5)),
+        Some(Range(Span::new(0, 8)))
+    );
+    assert_eq!(
+        Range(Span::new(0u8, 10)).merge(Span::new(2, 4)),
+        Some(Range(Span::new(0, 10)))
+    );
+    assert_eq!(
+        Range(Span::new(2u8, 4)).merge(Span::new(0, 10)),
+        Some(Range(Span::new(0, 10)))
+    );
+}
+
+#[test]
+fn split_range_inside_element() {
+    assert_eq!(
+        Range(Span::new(0, 9)).split_range(&Span::new(3, 5)),
+        Some((
+            Some(Range(Span::new(0, 2))),
+            Range(Span::new(3, 5)),
+            Some(Range(Span::new(6, 9)))
+        ))
     );
     assert_eq!(
-        Range(RangeInclusive::new(0, 3)).split(&1),
-        (Some(Single(0)), 1, Some(Range(RangeInclusive::new(2, 3))))
+        Range(Span::new(0, 2)).split_range(&Span::new(1, 1)),
+        Some((Some(Single(0)), Single(1), Some(Single(2))))
+    );
+}
+
+#[test]
+fn split_range_overlapping_element() {
+    assert_eq!(
+        Range(Span::new(5, 9)).split_range(&Span::new(0, 6)),
+        Some((None, Range(Span::new(5, 6)), Some(Range(Span::new(7, 9)))))
+    );
+    assert_eq!(
+        Range(Span::new(5, 9)).split_range(&Span::new(8, 20)),
+        Some((Some(Range(Span::new(5, 7))), Range(Span::new(8, 9)), None))
+    );
+    assert_eq!(
+        Range(Span::new(5, 9)).split_range(&Span::new(0, 20)),
+        Some((None, Range(Span::new(5, 9)), None))
+    );
+    assert_eq!(
+        Single(5).split_range(&Span::new(0, 20)),
+        Some((None, Single(5), None))
+    );
+}
+
+#[test]
+fn split_range_at_type_limits() {
+    assert_eq!(
+        Range(Span::new(0u8, 255)).split_range(&Span::new(0, 254)),
+        Some((None, Range(Span::new(0, 254)), Some(Single(255))))
     );
     assert_eq!(
-        Range(RangeInclusive::new(0, 3)).split(&2),
-        (Some(Range(RangeInclusive::new(0, 1))), 2, Some(Single(3)))
+        Range(Span::new(0u8, 255)).split_range(&Span::new(1, 255)),
+        Some((Some(Single(0)), Range(Span::new(1, 255)), None))
+    );
+}
+
+#[test]
+fn merge_disjoint_elements() {
+    assert_eq!(Single(0u8).merge(2), None);
+    assert_eq!(Range(Span::new(0u8, 3)).merge(5), None);
+    assert_eq!(Range(Span::new(0u8, 3)).merge(Span::new(8, 9)), None);
+    assert_eq!(Range(Span::new(8u8, 9)).merge(Span::new(0, 3)), None);
+    assert_eq!(Single(0u8).merge(255), None);
+}
+
+#[test]
+fn split_single_element() {
+    assert_eq!(Single(3).split(&3), Err(SplitError::Single));
+    assert_eq!(Single(3).split(&4), Err(SplitError::Single));
+}
+
+#[test]
+fn split_outside_element() {
+    assert_eq!(
+        Range(Span::new(3, 5)).split(&2),
+        Err(SplitError::OutOfRange)
     );
     assert_eq!(
-        Range(RangeInclusive::new(0, 3)).split(&3),
-        (Some(Range(RangeInclusive::new(0, 2))), 3, None)
+        Range(Span::new(3, 5)).split(&6),
+        Err(SplitError::OutOfRange)
     );
 }
+
+#[test]
+fn split_range_outside_element() {
+    assert_eq!(Range(Span::new(5, 9)).split_range(&Span::new(0, 4)), None);
+    assert_eq!(Range(Span::new(5, 9)).split_range(&Span::new(10, 20)), None);
+    assert_eq!(Single(5).split_range(&Span::new(6, 6)), None);
+    assert_eq!(Range(Span::new(5, 9)).split_range(&Span::new(8, 6)), None);
+}
@@ -0,0 +1,190 @@
+use crate::discrete::Discrete;
+use std::convert::TryFrom;
+
+/// Types that can be written in a set's binary encoding
+///
+/// `Encodable` maps each value to an unsigned number that is written as
+/// a variable-length integer. Unsigned types are written as they are,
+/// while signed types are zig-zag encoded first so that values close to
+/// zero stay short whichever side of it they're on. `char`s are written
+/// as their code points and `bool`s as `0` or `1`.
+///
+/// Every type also has a tag that is stored in the header, so data
+/// written for one type isn't silently read back as another.
+///
+/// For layouts that need to be searched without decoding, like the
+/// buffers behind a [`RangedSetRef`], values can also be written as
+/// fixed-width little-endian integers.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::Encodable;
+///
+/// assert_eq!(5u32.to_bits(), 5);
+/// assert_eq!((-1i32).to_bits(), 1);
+/// assert_eq!(1i32.to_bits(), 2);
+///
+/// assert_eq!(i32::from_bits(3), Some(-2));
+/// assert_eq!(u8::from_bits(256), None);
+///
+/// let mut bytes = [0; 2];
+/// 258u16.write_le(&mut bytes);
+/// assert_eq!(bytes, [2, 1]);
+/// assert_eq!(u16::read_le(&bytes), Some(258));
+/// ```
+///
+/// [`RangedSetRef`]: struct.RangedSetRef.html
+pub trait Encodable: Discrete {
+    /// The tag identifying the type in an encoded set
+    const TAG: u8;
+
+    /// The number of bytes the value takes up in a fixed-width layout
+    const WIDTH: usize;
+
+    /// Returns the number the value is written as
+    fn to_bits(&self) -> u128;
+
+    /// Returns the value a number read back stands for, or `None` if
+    /// it is outside the type's domain
+    fn from_bits(bits: u128) -> Option<Self>;
+
+    /// Writes the value as a little-endian integer into `bytes`, which
+    /// is `WIDTH` bytes long
+    fn write_le(&self, bytes: &mut [u8]);
+
+    /// Reads a value written by `write_le` back, or returns `None` if
+    /// `bytes` is the wrong length or the value is outside the type's
+    /// domain
+    fn read_le(bytes: &[u8]) -> Option<Self>;
+}
+
+// Tags hold the width of the type in bytes, with the high bit set for
+// signed types and the next bit set for types that aren't integers.
+// `usize` and `isize` are written as 64 bits wide so data written on
+// one platform can be read on another, as long as the values fit.
+
+macro_rules! unsigned_impl {
+    ($t:ty, $repr:ty, $width:expr) => {
+        impl Encodable for $t {
+            const TAG: u8 = $width;
+            const WIDTH: usize = $width;
+
+            fn to_bits(&self) -> u128 {
+                *self as u128
+            }
+
+            fn from_bits(bits: u128) -> Option<Self> {
+                <$t>::try_from(bits).ok()
+            }
+
+            fn write_le(&self, bytes: &mut [u8]) {
+                bytes.copy_from_slice(&(*self as $repr).to_le_bytes());
+            }
+
+            fn read_le(bytes: &[u8]) -> Option<Self> {
+                let bytes = <[u8; $width]>::try_from(bytes).ok()?;
+
+                <$t>::try_from(<$repr>::from_le_bytes(bytes)).ok()
+            }
+        }
+    };
+}
+
+macro_rules! signed_impl {
+    ($t:ty, $repr:ty, $width:expr) => {
+        impl Encodable for $t {
+            const TAG: u8 = 0x80 | $width;
+            const WIDTH: usize = $width;
+
+            fn to_bits(&self) -> u128 {
+                let value = *self as i128;
+
+                ((value << 1) ^ (value >> 127)) as u128
+            }
+
+            fn from_bits(bits: u128) -> Option<Self> {
+                let value = (bits >> 1) as i128 ^ -((bits & 1) as i128);
+
+                <$t>::try_from(value).ok()
+            }
+
+            fn write_le(&self, bytes: &mut [u8]) {
+                bytes.copy_from_slice(&(*self as $repr).to_le_bytes());
+            }
+
+            fn read_le(bytes: &[u8]) -> Option<Self> {
+                let bytes = <[u8; $width]>::try_from(bytes).ok()?;
+
+                <$t>::try_from(<$repr>::from_le_bytes(bytes)).ok()
+            }
+        }
+    };
+}
+
+signed_impl!(i8, i8, 1);
+signed_impl!(i16, i16, 2);
+signed_impl!(i32, i32, 4);
+signed_impl!(i64, i64, 8);
+signed_impl!(i128, i128, 16);
+signed_impl!(isize, i64, 8);
+unsigned_impl!(u8, u8, 1);
+unsigned_impl!(u16, u16, 2);
+unsigned_impl!(u32, u32, 4);
+unsigned_impl!(u64, u64, 8);
+unsigned_impl!(u128, u128, 16);
+unsigned_impl!(usize, u64, 8);
+
+/// `char`s are written as their code points, so surrogates are rejected
+/// when reading like any other value outside the domain.
+impl Encodable for char {
+    const TAG: u8 = 0x40 | 4;
+    const WIDTH: usize = 4;
+
+    fn to_bits(&self) -> u128 {
+        *self as u128
+    }
+
+    fn from_bits(bits: u128) -> Option<Self> {
+        char::from_u32(u32::try_from(bits).ok()?)
+    }
+
+    fn write_le(&self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&(*self as u32).to_le_bytes());
+    }
+
+    fn read_le(bytes: &[u8]) -> Option<Self> {
+        let bytes = <[u8; 4]>::try_from(bytes).ok()?;
+
+        char::from_u32(u32::from_le_bytes(bytes))
+    }
+}
+
+impl Encodable for bool {
+    const TAG: u8 = 0x40 | 1;
+    const WIDTH: usize = 1;
+
+    fn to_bits(&self) -> u128 {
+        *self as u128
+    }
+
+    fn from_bits(bits: u128) -> Option<Self> {
+        match bits {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    fn write_le(&self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&[*self as u8]);
+    }
+
+    fn read_le(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0] => Some(false),
+            [1] => Some(true),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,393 @@
+#[cfg(test)]
+mod tests;
+
+use crate::discrete::Discrete;
+use crate::element::Element;
+use crate::span::Span;
+use std::iter::FusedIterator;
+use std::{ops, slice, vec};
+
+/// Steps through the values of a single element in either direction
+#[derive(Clone, Debug)]
+pub(crate) struct Values<T> {
+    bounds: Option<Span<T>>,
+}
+
+impl<T: Discrete> Values<T> {
+    pub(crate) fn empty() -> Self {
+        Values { bounds: None }
+    }
+
+    fn new(element: &Element<T>) -> Self {
+        Values::spanning(Span::new(element.start().clone(), element.end().clone()))
+    }
+
+    pub(crate) fn spanning(span: Span<T>) -> Self {
+        Values { bounds: Some(span) }
+    }
+
+    fn clipped(element: &Element<T>, window: &Span<T>) -> Self {
+        Values {
+            bounds: element
+                .split_range(window)
+                .map(|(_, inside, _)| Span::new(inside.start().clone(), inside.end().clone())),
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> Option<T> {
+        let Span { start, end } = self.bounds.take()?;
+        if start < end {
+            self.bounds = start.successor().map(|n| Span::new(n, end));
+        }
+
+        Some(start)
+    }
+
+    pub(crate) fn next_back(&mut self) -> Option<T> {
+        let Span { start, end } = self.bounds.take()?;
+        if start < end {
+            self.bounds = end.predecessor().map(|p| Span::new(start, p));
+        }
+
+        Some(end)
+    }
+
+    pub(crate) fn len(&self) -> u128 {
+        match self.bounds {
+            Some(ref b) => b.start.distance(&b.end).saturating_add(1),
+            None => 0,
+        }
+    }
+}
+
+pub(crate) fn size_hint(count: u128) -> (usize, Option<usize>) {
+    if count <= usize::MAX as u128 {
+        (count as usize, Some(count as usize))
+    } else {
+        (usize::MAX, None)
+    }
+}
+
+/// An iterator over the values of a [`RangedSet`]
+///
+/// This `struct` is created by the [`iter`] and [`range`] methods on
+/// [`RangedSet`].
+///
+/// [`iter`]: struct.RangedSet.html#method.iter
+/// [`range`]: struct.RangedSet.html#method.range
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Debug)]
+pub struct Iter<'a, T: Discrete> {
+    elements: slice::Iter<'a, Element<T>>,
+    front: Values<T>,
+    back: Values<T>,
+}
+
+impl<'a, T: Discrete> Iter<'a, T> {
+    pub(crate) fn new(elements: &'a [Element<T>]) -> Self {
+        Iter {
+            elements: elements.iter(),
+            front: Values::empty(),
+            back: Values::empty(),
+        }
+    }
+
+    /// Starts iterating with the elements at either end already clipped
+    /// to a window, so only the values inside it are produced.
+    pub(crate) fn within(elements: &'a [Element<T>], window: Span<T>) -> Self {
+        let (front, elements, back) = match elements.split_first() {
+            Some((first, rest)) => match rest.split_last() {
+                Some((last, middle)) => (first, middle, Some(last)),
+                None => (first, rest, None),
+            },
+            None => return Iter::new(elements),
+        };
+
+        Iter {
+            elements: elements.iter(),
+            front: Values::clipped(front, &window),
+            back: match back {
+                Some(back) => Values::clipped(back, &window),
+                None => Values::empty(),
+            },
+        }
+    }
+}
+
+impl<'a, T: Discrete> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(value) = self.front.next() {
+                return Some(value);
+            }
+
+            match self.elements.next() {
+                Some(element) => self.front = Values::new(element),
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let elements = self
+            .elements
+            .clone()
+            .map(Element::len)
+            .fold(0, u128::saturating_add);
+
+        size_hint(
+            self.front
+                .len()
+                .saturating_add(elements)
+                .saturating_add(self.back.len()),
+        )
+    }
+}
+
+impl<'a, T: Discrete> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<T> {
+        loop {
+            if let Some(value) = self.back.next_back() {
+                return Some(value);
+            }
+
+            match self.elements.next_back() {
+                Some(element) => self.back = Values::new(element),
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<'a, T: Discrete> FusedIterator for Iter<'a, T> {}
+
+/// An owning iterator over the values of a [`RangedSet`]
+///
+/// This `struct` is created by the `into_iter` method on [`RangedSet`]
+/// (provided by the `IntoIterator` trait).
+///
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Debug)]
+pub struct IntoIter<T: Discrete> {
+    elements: vec::IntoIter<Element<T>>,
+    front: Values<T>,
+    back: Values<T>,
+}
+
+impl<T: Discrete> IntoIter<T> {
+    pub(crate) fn new(elements: Vec<Element<T>>) -> Self {
+        IntoIter {
+            elements: elements.into_iter(),
+            front: Values::empty(),
+            back: Values::empty(),
+        }
+    }
+}
+
+impl<T: Discrete> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(value) = self.front.next() {
+                return Some(value);
+            }
+
+            match self.elements.next() {
+                Some(element) => self.front = Values::new(&element),
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let elements = self
+            .elements
+            .as_slice()
+            .iter()
+            .map(Element::len)
+            .fold(0, u128::saturating_add);
+
+        size_hint(
+            self.front
+                .len()
+                .saturating_add(elements)
+                .saturating_add(self.back.len()),
+        )
+    }
+}
+
+impl<T: Discrete> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        loop {
+            if let Some(value) = self.back.next_back() {
+                return Some(value);
+            }
+
+            match self.elements.next_back() {
+                Some(element) => self.back = Values::new(&element),
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<T: Discrete> FusedIterator for IntoIter<T> {}
+
+// Like the standard library's `RangeInclusive`, only the types whose
+// whole domain can be counted in a `usize` get an exact size.
+macro_rules! exact_size_impl {
+    ($t:ty) => {
+        impl<'a> ExactSizeIterator for Iter<'a, $t> {}
+        impl ExactSizeIterator for IntoIter<$t> {}
+    };
+}
+
+exact_size_impl!(i8);
+exact_size_impl!(i16);
+exact_size_impl!(u8);
+exact_size_impl!(u16);
+
+/// An iterator over the contiguous ranges of values in a [`RangedSet`]
+///
+/// This `struct` is created by the [`ranges`] and [`ranges_in`] methods
+/// on [`RangedSet`].
+///
+/// [`ranges`]: struct.RangedSet.html#method.ranges
+/// [`ranges_in`]: struct.RangedSet.html#method.ranges_in
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Debug)]
+pub struct Ranges<'a, T: Discrete> {
+    elements: slice::Iter<'a, Element<T>>,
+    window: Option<Span<T>>,
+}
+
+impl<'a, T: Discrete> Ranges<'a, T> {
+    pub(crate) fn new(elements: &'a [Element<T>]) -> Self {
+        Ranges {
+            elements: elements.iter(),
+            window: None,
+        }
+    }
+
+    pub(crate) fn within(elements: &'a [Element<T>], window: Span<T>) -> Self {
+        Ranges {
+            elements: elements.iter(),
+            window: Some(window),
+        }
+    }
+
+    fn clip(&self, element: &Element<T>) -> ops::RangeInclusive<T> {
+        let (mut start, mut end) = (element.start(), element.end());
+
+        if let Some(ref window) = self.window {
+            start = std::cmp::max(start, &window.start);
+            end = std::cmp::min(end, &window.end);
+        }
+
+        start.clone()..=end.clone()
+    }
+}
+
+impl<'a, T: Discrete> Iterator for Ranges<'a, T> {
+    type Item = ops::RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = self.elements.next()?;
+
+        Some(self.clip(element))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
+
+impl<'a, T: Discrete> DoubleEndedIterator for Ranges<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let element = self.elements.next_back()?;
+
+        Some(self.clip(element))
+    }
+}
+
+impl<'a, T: Discrete> ExactSizeIterator for Ranges<'a, T> {}
+
+impl<'a, T: Discrete> FusedIterator for Ranges<'a, T> {}
+
+/// An iterator over the ranges of values missing from a [`RangedSet`]
+///
+/// This `struct` is created by the [`gaps`] and [`gaps_within`] methods
+/// on [`RangedSet`].
+///
+/// [`gaps`]: struct.RangedSet.html#method.gaps
+/// [`gaps_within`]: struct.RangedSet.html#method.gaps_within
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Debug)]
+pub struct Gaps<'a, T: Discrete> {
+    elements: slice::Iter<'a, Element<T>>,
+    start: Option<T>,
+    end: Option<T>,
+}
+
+impl<'a, T: Discrete> Gaps<'a, T> {
+    pub(crate) fn between(elements: &'a [Element<T>]) -> Self {
+        match elements.split_first() {
+            Some((first, rest)) => Gaps {
+                elements: rest.iter(),
+                start: first.end().successor(),
+                end: None,
+            },
+            None => Gaps {
+                elements: elements.iter(),
+                start: None,
+                end: None,
+            },
+        }
+    }
+
+    pub(crate) fn within(elements: &'a [Element<T>], window: Option<Span<T>>) -> Self {
+        let (start, end) = match window {
+            Some(w) => (Some(w.start), Some(w.end)),
+            None => (None, None),
+        };
+
+        Gaps {
+            elements: elements.iter(),
+            start,
+            end,
+        }
+    }
+}
+
+impl<'a, T: Discrete> Iterator for Gaps<'a, T> {
+    type Item = ops::RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = self.start.take()?;
+
+            match self.elements.next() {
+                Some(element) => {
+                    self.start = element.end().successor();
+
+                    if start < *element.start() {
+                        if let Some(end) = element.start().predecessor() {
+                            return Some(start..=end);
+                        }
+                    }
+                }
+                None => {
+                    return match self.end.take() {
+                        Some(end) if start <= end => Some(start..=end),
+                        _ => None,
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T: Discrete> FusedIterator for Gaps<'a, T> {}
@@ -0,0 +1,146 @@
+use crate::element::Element::{Range, Single};
+use crate::iter::{Gaps, IntoIter, Iter, Ranges};
+use crate::span::Span;
+
+#[test]
+fn iter_over_no_elements() {
+    let elements: Vec<crate::element::Element<u8>> = vec![];
+
+    assert_eq!(Iter::new(&elements).next(), None);
+    assert_eq!(Iter::new(&elements).next_back(), None);
+    assert_eq!(Iter::new(&elements).len(), 0);
+    assert_eq!(Ranges::new(&elements).next(), None);
+}
+
+#[test]
+fn iter_over_mixed_elements() {
+    let elements = vec![Single(0), Range(Span::new(2, 4)), Single(7)];
+
+    assert_eq!(
+        Iter::new(&elements).collect::<Vec<_>>(),
+        vec![0, 2, 3, 4, 7]
+    );
+    assert_eq!(
+        Iter::new(&elements).rev().collect::<Vec<_>>(),
+        vec![7, 4, 3, 2, 0]
+    );
+    assert_eq!(
+        IntoIter::new(elements.clone()).collect::<Vec<_>>(),
+        vec![0, 2, 3, 4, 7]
+    );
+    assert_eq!(
+        IntoIter::new(elements).rev().collect::<Vec<_>>(),
+        vec![7, 4, 3, 2, 0]
+    );
+}
+
+#[test]
+fn iter_from_both_ends() {
+    let elements = vec![Range(Span::new(0u8, 3)), Single(6)];
+    let mut iter = Iter::new(&elements);
+
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next_back(), Some(6));
+    assert_eq!(iter.next_back(), Some(3));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(2));
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn iter_within_one_element_from_both_ends() {
+    let mut iter = IntoIter::new(vec![Range(Span::new(0u8, 2))]);
+
+    assert_eq!(iter.next_back(), Some(2));
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next_back(), Some(1));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn iter_at_type_limits() {
+    let elements = vec![Range(Span::new(0u8, 255))];
+
+    assert_eq!(Iter::new(&elements).len(), 256);
+    assert_eq!(Iter::new(&elements).nth(255), Some(255));
+    assert_eq!(Iter::new(&elements).rev().nth(255), Some(0));
+    assert_eq!(Iter::new(&elements).count(), 256);
+}
+
+#[test]
+fn iter_size_hint_beyond_usize() {
+    let elements = vec![Range(Span::new(0u64, u64::MAX))];
+
+    assert_eq!(Iter::new(&elements).size_hint(), (usize::MAX, None));
+}
+
+#[test]
+fn ranges_over_mixed_elements() {
+    let elements = vec![Single(0), Range(Span::new(2, 4)), Single(7)];
+    let mut ranges = Ranges::new(&elements);
+
+    assert_eq!(ranges.len(), 3);
+    assert_eq!(ranges.next(), Some(0..=0));
+    assert_eq!(ranges.next_back(), Some(7..=7));
+    assert_eq!(ranges.next(), Some(2..=4));
+    assert_eq!(ranges.next(), None);
+    assert_eq!(ranges.next_back(), None);
+}
+
+#[test]
+fn gaps_between_elements() {
+    let empty: Vec<crate::element::Element<u8>> = vec![];
+    let single = vec![Range(Span::new(2, 5))];
+    let mixed = vec![
+        Single(0),
+        Range(Span::new(2, 5)),
+        Single(9),
+        Range(Span::new(11, 12)),
+    ];
+
+    assert_eq!(Gaps::between(&empty).next(), None);
+    assert_eq!(Gaps::between(&single).next(), None);
+    assert_eq!(
+        Gaps::between(&mixed).collect::<Vec<_>>(),
+        vec![1..=1, 6..=8, 10..=10]
+    );
+}
+
+#[test]
+fn gaps_within_window() {
+    let elements = vec![Range(Span::new(2, 5)), Single(9)];
+
+    assert_eq!(
+        Gaps::within(&elements, Some(Span::new(0, 12))).collect::<Vec<_>>(),
+        vec![0..=1, 6..=8, 10..=12]
+    );
+    assert_eq!(
+        Gaps::within(&elements, Some(Span::new(2, 9))).collect::<Vec<_>>(),
+        vec![6..=8]
+    );
+    assert_eq!(
+        Gaps::within(&[], Some(Span::new(3, 7))).collect::<Vec<_>>(),
+        vec![3..=7]
+    );
+    assert_eq!(Gaps::within(&elements, None).next(), None);
+}
+
+#[test]
+fn gaps_at_type_limits() {
+    let full = vec![Range(Span::new(0u8, 255))];
+    let ends = vec![Single(0u8), Single(255)];
+
+    assert_eq!(Gaps::between(&full).next(), None);
+    assert_eq!(Gaps::within(&full, Some(Span::new(0, 255))).next(), None);
+    assert_eq!(Gaps::between(&ends).collect::<Vec<_>>(), vec![1..=254]);
+    assert_eq!(
+        Gaps::within(&ends, Some(Span::new(0, 255))).collect::<Vec<_>>(),
+        vec![1..=254]
+    );
+}
@@ -45,9 +45,36 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Features
+//!
+//! - `serde`: implements `Serialize` and `Deserialize` for `RangedSet`, writing
+//!   it as a sequence of `[start, end]` pairs.
 
+mod algebra;
+mod binary;
+mod counter;
+mod discrete;
 mod element;
-mod range_inclusive;
+mod encodable;
+mod iter;
+mod map;
+#[cfg(feature = "serde")]
+mod serialization;
 mod set;
+mod span;
+mod text;
+mod unicode;
+mod view;
 
+pub use crate::algebra::{Difference, Intersection, SymmetricDifference, Union};
+pub use crate::binary::DecodeError;
+pub use crate::counter::{Counts, RangedCounter};
+pub use crate::discrete::Discrete;
+pub use crate::encodable::Encodable;
+pub use crate::iter::{Gaps, IntoIter, Iter, Ranges};
+pub use crate::map::{Entries, RangedMap};
 pub use crate::set::RangedSet;
+pub use crate::span::Span;
+pub use crate::text::{ParseError, ParseErrorKind};
+pub use crate::view::{RangedSetRef, RefIter, RefRanges};
@@ -0,0 +1,483 @@
+#[cfg(test)]
+mod tests;
+
+use crate::discrete::Discrete;
+use crate::element::Element;
+use crate::span::Span;
+use std::iter::FusedIterator;
+use std::ops::{self, RangeBounds};
+use std::slice;
+
+/// A map from ranges of keys to values
+///
+/// `RangedMap` stores a value for each contiguous range of keys rather
+/// than for each key. Adjacent ranges that map to equal values are
+/// merged automatically, so the map stays as compact as the values
+/// allow.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::RangedMap;
+///
+/// let mut owners = RangedMap::new();
+/// owners.insert_range(0..=99, "alice");
+/// owners.insert_range(100..=199, "bob");
+/// owners.insert_range(50..=149, "carol");
+///
+/// assert_eq!(owners.get(&10), Some(&"alice"));
+/// assert_eq!(owners.get(&120), Some(&"carol"));
+/// assert_eq!(owners.get(&180), Some(&"bob"));
+/// assert_eq!(owners.get(&200), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RangedMap<K: Discrete, V> {
+    entries: Vec<(Element<K>, V)>,
+}
+
+impl<K: Discrete, V> RangedMap<K, V> {
+    /// Returns a new empty map
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    /// let mut map: RangedMap<i32, &str> = RangedMap::new();
+    /// ```
+    pub fn new() -> RangedMap<K, V> {
+        RangedMap {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the map contains no keys
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    ///
+    /// let mut map = RangedMap::new();
+    /// assert_eq!(map.is_empty(), true);
+    ///
+    /// map.insert(1, "a");
+    /// assert_eq!(map.is_empty(), false);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of ranges of keys stored in the map
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    ///
+    /// let mut map = RangedMap::new();
+    /// map.insert_range(0..=9, "a");
+    /// map.insert_range(10..=19, "a");
+    /// map.insert_range(20..=29, "b");
+    /// assert_eq!(map.range_count(), 2);
+    /// ```
+    pub fn range_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map contains a value for a key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    ///
+    /// let mut map = RangedMap::new();
+    /// map.insert_range(0..=9, "a");
+    ///
+    /// assert_eq!(map.contains_key(&5), true);
+    /// assert_eq!(map.contains_key(&10), false);
+    /// ```
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find_index_for(key).is_ok()
+    }
+
+    /// Returns the value for a key
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    ///
+    /// let mut map = RangedMap::new();
+    /// map.insert_range(0..=9, "a");
+    ///
+    /// assert_eq!(map.get(&5), Some(&"a"));
+    /// assert_eq!(map.get(&10), None);
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let index = self.find_index_for(key).ok()?;
+
+        Some(&self.entries[index].1)
+    }
+
+    /// Returns the value for a key along with the whole range of keys
+    /// that map to it
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    ///
+    /// let mut map = RangedMap::new();
+    /// map.insert_range(0..=9, "a");
+    ///
+    /// assert_eq!(map.get_range(&5), Some((0..=9, &"a")));
+    /// assert_eq!(map.get_range(&10), None);
+    /// ```
+    pub fn get_range(&self, key: &K) -> Option<(ops::RangeInclusive<K>, &V)> {
+        let index = self.find_index_for(key).ok()?;
+        let (ref element, ref value) = self.entries[index];
+
+        Some((element.start().clone()..=element.end().clone(), value))
+    }
+
+    /// Returns an iterator over the ranges of keys in the map and their
+    /// values
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    ///
+    /// let mut map = RangedMap::new();
+    /// map.insert_range(0..=9, "a");
+    /// map.insert(20, "b");
+    ///
+    /// let entries: Vec<_> = map.iter().collect();
+    /// assert_eq!(entries, vec![(0..=9, &"a"), (20..=20, &"b")]);
+    /// ```
+    pub fn iter(&self) -> Entries<'_, K, V> {
+        Entries::new(&self.entries, None)
+    }
+
+    /// Returns an iterator over the ranges of keys in a range and their
+    /// values
+    ///
+    /// The ranges of keys at either end are clipped to the range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    ///
+    /// let mut map = RangedMap::new();
+    /// map.insert_range(0..=9, "a");
+    /// map.insert_range(10..=19, "b");
+    /// map.insert_range(30..=39, "c");
+    ///
+    /// let entries: Vec<_> = map.range(5..15).collect();
+    /// assert_eq!(entries, vec![(5..=9, &"a"), (10..=14, &"b")]);
+    /// ```
+    pub fn range<R>(&self, range: R) -> Entries<'_, K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        match Span::from_bounds(&range) {
+            Some(range) => self.entries_in(range),
+            None => Entries::new(&[], None),
+        }
+    }
+
+    fn entries_in(&self, range: Span<K>) -> Entries<'_, K, V> {
+        let first = match self.find_index_for(&range.start) {
+            Ok(index) | Err(index) => index,
+        };
+        let last = match self.find_index_for(&range.end) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        };
+
+        Entries::new(&self.entries[first..last], Some(range))
+    }
+
+    /// Removes the values for every key in a range
+    ///
+    /// Ranges of keys at either end are trimmed (or split in two if the
+    /// range lands in the middle of one). Returns `true` if any keys
+    /// were removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    ///
+    /// let mut map = RangedMap::new();
+    /// map.insert_range(0..=9, "a");
+    ///
+    /// assert_eq!(map.remove_range(3..=5), true);
+    /// assert_eq!(map.get(&4), None);
+    /// assert_eq!(map.get(&6), Some(&"a"));
+    /// ```
+    pub fn remove_range<R>(&mut self, range: R) -> bool
+    where
+        R: RangeBounds<K>,
+        V: Clone,
+    {
+        let range = match Span::from_bounds(&range) {
+            Some(range) => range,
+            None => return false,
+        };
+
+        let (_, removed) = self.cut(&range);
+        removed
+    }
+
+    /// Maps every key in a range to a value
+    ///
+    /// Any values already stored for keys in the range are replaced,
+    /// splitting the ranges of keys at either end if the range only
+    /// covers part of them. The range is then merged with its
+    /// neighbours if they are adjacent and map to an equal value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    ///
+    /// let mut map = RangedMap::new();
+    /// map.insert_range(0..=9, "a");
+    /// map.insert_range(3..=5, "b");
+    /// map.insert_range(3..=5, "a");
+    ///
+    /// let entries: Vec<_> = map.iter().collect();
+    /// assert_eq!(entries, vec![(0..=9, &"a")]);
+    /// ```
+    pub fn insert_range<R>(&mut self, range: R, value: V)
+    where
+        R: RangeBounds<K>,
+        V: Clone + PartialEq,
+    {
+        if let Some(range) = Span::from_bounds(&range) {
+            self.insert_span(range, value);
+        }
+    }
+
+    fn insert_span(&mut self, range: Span<K>, value: V)
+    where
+        V: Clone + PartialEq,
+    {
+        let (index, _) = self.cut(&range);
+        self.entries
+            .insert(index, (Element::from_bounds(range.start, range.end), value));
+
+        self.coalesce(index);
+    }
+
+    /// Maps a single key to a value
+    ///
+    /// This is the same as calling [`insert_range`] with a range only
+    /// containing the key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedMap;
+    ///
+    /// let mut map = RangedMap::new();
+    /// map.insert(0, "a");
+    /// map.insert(1, "a");
+    ///
+    /// assert_eq!(map.get_range(&0), Some((0..=1, &"a")));
+    /// ```
+    ///
+    /// [`insert_range`]: #method.insert_range
+    pub fn insert(&mut self, key: K, value: V)
+    where
+        V: Clone + PartialEq,
+    {
+        self.insert_span(Span::new(key.clone(), key), value);
+    }
+
+    /// Replaces the value of every key in a range with the result of a
+    /// function
+    ///
+    /// The function is called once for each range of keys within the
+    /// range that share a value, and once for each gap between them
+    /// (with `None`). Returning `None` leaves those keys without a
+    /// value.
+    pub(crate) fn update_range<F>(&mut self, range: Span<K>, mut f: F)
+    where
+        F: FnMut(Option<&V>) -> Option<V>,
+        V: Clone + PartialEq,
+    {
+        let mut pieces = Vec::new();
+        let mut cursor = Some(range.start.clone());
+        for (span, value) in self.entries_in(range.clone()) {
+            let (start, end) = span.into_inner();
+            if let Some(gap_start) = cursor.take() {
+                if gap_start < start {
+                    if let Some(gap_end) = start.predecessor() {
+                        pieces.push((Element::from_bounds(gap_start, gap_end), f(None)));
+                    }
+                }
+            }
+
+            cursor = end.successor();
+            pieces.push((Element::from_bounds(start, end), f(Some(value))));
+        }
+        if let Some(gap_start) = cursor {
+            if gap_start <= range.end {
+                pieces.push((Element::from_bounds(gap_start, range.end.clone()), f(None)));
+            }
+        }
+
+        let (mut index, _) = self.cut(&range);
+        for (element, value) in pieces {
+            if let Some(value) = value {
+                self.entries.insert(index, (element, value));
+                index = self.coalesce(index) + 1;
+            }
+        }
+    }
+
+    /// Removes every key in a range from the entries, keeping whatever
+    /// is left over from the entries at either end. Returns the index a
+    /// new entry for the range would go at, and whether any keys were
+    /// removed.
+    fn cut(&mut self, range: &Span<K>) -> (usize, bool)
+    where
+        V: Clone,
+    {
+        let first = match self.find_index_for(&range.start) {
+            Ok(index) | Err(index) => index,
+        };
+        let last = match self.find_index_for(&range.end) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        };
+
+        let mut prev = None;
+        let mut next = None;
+        for (element, value) in self.entries.drain(first..last) {
+            let (p, _, n) = match element.split_range(range) {
+                Some(parts) => parts,
+                None => continue,
+            };
+            if let Some(p) = p {
+                prev = Some((p, value.clone()));
+            }
+            if let Some(n) = n {
+                next = Some((n, value));
+            }
+        }
+
+        let mut index = first;
+        if let Some(prev) = prev {
+            self.entries.insert(index, prev);
+            index += 1;
+        }
+        if let Some(next) = next {
+            self.entries.insert(index, next);
+        }
+
+        (index, first != last)
+    }
+
+    /// Merges the entry at an index with its neighbours if they are
+    /// adjacent and have an equal value. Returns the index the entry
+    /// ends up at.
+    fn coalesce(&mut self, index: usize) -> usize
+    where
+        V: PartialEq,
+    {
+        if let Some((next, value)) = self.entries.get(index + 1) {
+            if *value == self.entries[index].1 {
+                if let Some(merged) = self.entries[index].0.clone().merge(next.clone()) {
+                    self.entries[index].0 = merged;
+                    self.entries.remove(index + 1);
+                }
+            }
+        }
+
+        if let Some(before) = index.checked_sub(1) {
+            let (prev, value) = &self.entries[before];
+            if *value == self.entries[index].1 {
+                if let Some(merged) = prev.clone().merge(self.entries[index].0.clone()) {
+                    self.entries[before].0 = merged;
+                    self.entries.remove(index);
+
+                    return before;
+                }
+            }
+        }
+
+        index
+    }
+
+    fn find_index_for(&self, key: &K) -> Result<usize, usize> {
+        self.entries
+            .binary_search_by(|(member, _)| member.cmp_value(key))
+    }
+}
+
+/// An iterator over the ranges of keys in a [`RangedMap`] and their
+/// values
+///
+/// This `struct` is created by the [`iter`] and [`range`] methods on
+/// [`RangedMap`].
+///
+/// [`iter`]: struct.RangedMap.html#method.iter
+/// [`range`]: struct.RangedMap.html#method.range
+/// [`RangedMap`]: struct.RangedMap.html
+#[derive(Clone, Debug)]
+pub struct Entries<'a, K: Discrete, V> {
+    entries: slice::Iter<'a, (Element<K>, V)>,
+    bounds: Option<Span<K>>,
+}
+
+impl<'a, K: Discrete, V> Entries<'a, K, V> {
+    fn new(entries: &'a [(Element<K>, V)], bounds: Option<Span<K>>) -> Self {
+        Entries {
+            entries: entries.iter(),
+            bounds,
+        }
+    }
+
+    fn clip(&self, entry: &'a (Element<K>, V)) -> (ops::RangeInclusive<K>, &'a V) {
+        let (ref element, ref value) = *entry;
+        let (mut start, mut end) = (element.start(), element.end());
+
+        if let Some(ref bounds) = self.bounds {
+            start = std::cmp::max(start, &bounds.start);
+            end = std::cmp::min(end, &bounds.end);
+        }
+
+        (start.clone()..=end.clone(), value)
+    }
+}
+
+impl<'a, K: Discrete, V> Iterator for Entries<'a, K, V> {
+    type Item = (ops::RangeInclusive<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.next()?;
+
+        Some(self.clip(entry))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a, K: Discrete, V> DoubleEndedIterator for Entries<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.next_back()?;
+
+        Some(self.clip(entry))
+    }
+}
+
+impl<'a, K: Discrete, V> ExactSizeIterator for Entries<'a, K, V> {}
+
+impl<'a, K: Discrete, V> FusedIterator for Entries<'a, K, V> {}
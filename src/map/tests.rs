@@ -0,0 +1,301 @@
+use crate::element::Element::{Range, Single};
+use crate::map::RangedMap;
+use crate::span::Span;
+
+#[test]
+fn insert_range_into_empty_map() {
+    let mut map = RangedMap::new();
+    map.insert_range(2..=5, 'a');
+
+    assert_eq!(map.entries, vec![(Range(Span::new(2, 5)), 'a')]);
+}
+
+#[test]
+fn insert_range_with_empty_range() {
+    let mut map = RangedMap::new();
+    let (start, end) = (5, 2);
+    map.insert_range(start..=end, 'a');
+    map.insert_range(3..3, 'a');
+
+    assert!(map.is_empty());
+}
+
+#[test]
+fn insert_single_key_stores_single_element() {
+    let mut map = RangedMap::new();
+    map.insert(4, 'a');
+
+    assert_eq!(map.entries, vec![(Single(4), 'a')]);
+}
+
+#[test]
+fn insert_range_splits_overlapped_range() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=9, 'a');
+    map.insert_range(4..=5, 'b');
+
+    assert_eq!(
+        map.entries,
+        vec![
+            (Range(Span::new(0, 3)), 'a'),
+            (Range(Span::new(4, 5)), 'b'),
+            (Range(Span::new(6, 9)), 'a'),
+        ]
+    );
+}
+
+#[test]
+fn insert_range_trims_partially_overlapped_ranges() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=4, 'a');
+    map.insert_range(6..=9, 'b');
+    map.insert_range(3..=7, 'c');
+
+    assert_eq!(
+        map.entries,
+        vec![
+            (Range(Span::new(0, 2)), 'a'),
+            (Range(Span::new(3, 7)), 'c'),
+            (Range(Span::new(8, 9)), 'b'),
+        ]
+    );
+}
+
+#[test]
+fn insert_range_replaces_covered_ranges() {
+    let mut map = RangedMap::new();
+    map.insert_range(2..=3, 'a');
+    map.insert(5, 'b');
+    map.insert_range(7..=8, 'c');
+    map.insert_range(0..=10, 'd');
+
+    assert_eq!(map.entries, vec![(Range(Span::new(0, 10)), 'd')]);
+}
+
+#[test]
+fn insert_range_coalesces_with_equal_neighbours() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=2, 'a');
+    map.insert_range(6..=8, 'a');
+    map.insert_range(3..=5, 'a');
+
+    assert_eq!(map.entries, vec![(Range(Span::new(0, 8)), 'a')]);
+}
+
+#[test]
+fn insert_range_does_not_coalesce_with_different_neighbours() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=2, 'a');
+    map.insert_range(6..=8, 'b');
+    map.insert_range(3..=5, 'c');
+
+    assert_eq!(map.range_count(), 3);
+}
+
+#[test]
+fn insert_range_restores_split_range() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=9, 'a');
+    map.insert(5, 'b');
+    map.insert(5, 'a');
+
+    assert_eq!(map.entries, vec![(Range(Span::new(0, 9)), 'a')]);
+}
+
+#[test]
+fn insert_range_at_edges_of_domain() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=255u8, 'a');
+    map.insert(0, 'b');
+    map.insert(255, 'c');
+
+    assert_eq!(
+        map.entries,
+        vec![
+            (Single(0), 'b'),
+            (Range(Span::new(1, 254)), 'a'),
+            (Single(255), 'c'),
+        ]
+    );
+}
+
+#[test]
+fn get_finds_values() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=4, 'a');
+    map.insert_range(8..=9, 'b');
+
+    assert_eq!(map.get(&-1), None);
+    assert_eq!(map.get(&0), Some(&'a'));
+    assert_eq!(map.get(&4), Some(&'a'));
+    assert_eq!(map.get(&5), None);
+    assert_eq!(map.get(&8), Some(&'b'));
+    assert_eq!(map.get(&10), None);
+    assert!(map.contains_key(&9));
+    assert!(!map.contains_key(&7));
+}
+
+#[test]
+fn get_range_returns_whole_range() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=4, 'a');
+    map.insert(6, 'b');
+
+    assert_eq!(map.get_range(&2), Some((0..=4, &'a')));
+    assert_eq!(map.get_range(&6), Some((6..=6, &'b')));
+    assert_eq!(map.get_range(&5), None);
+}
+
+#[test]
+fn remove_range_splits_range() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=9, 'a');
+
+    assert!(map.remove_range(3..=5));
+    assert_eq!(
+        map.entries,
+        vec![(Range(Span::new(0, 2)), 'a'), (Range(Span::new(6, 9)), 'a'),]
+    );
+}
+
+#[test]
+fn remove_range_across_ranges() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=4, 'a');
+    map.insert(6, 'b');
+    map.insert_range(8..=12, 'c');
+
+    assert!(map.remove_range(3..=9));
+    assert_eq!(
+        map.entries,
+        vec![
+            (Range(Span::new(0, 2)), 'a'),
+            (Range(Span::new(10, 12)), 'c'),
+        ]
+    );
+}
+
+#[test]
+fn remove_range_with_no_keys() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=4, 'a');
+    map.insert_range(8..=12, 'c');
+
+    assert!(!map.remove_range(5..=7));
+    assert!(!map.remove_range(20..30));
+    assert_eq!(map.range_count(), 2);
+}
+
+#[test]
+fn iter_yields_entries_in_both_directions() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=4, 'a');
+    map.insert(6, 'b');
+    map.insert_range(8..=12, 'c');
+
+    let mut iter = map.iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next(), Some((0..=4, &'a')));
+    assert_eq!(iter.next_back(), Some((8..=12, &'c')));
+    assert_eq!(iter.next(), Some((6..=6, &'b')));
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn range_clips_entries_at_either_end() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=4, 'a');
+    map.insert(6, 'b');
+    map.insert_range(8..=12, 'c');
+
+    let entries: Vec<_> = map.range(2..=10).collect();
+    assert_eq!(entries, vec![(2..=4, &'a'), (6..=6, &'b'), (8..=10, &'c')]);
+
+    let entries: Vec<_> = map.range(2..=10).rev().collect();
+    assert_eq!(entries, vec![(8..=10, &'c'), (6..=6, &'b'), (2..=4, &'a')]);
+}
+
+#[test]
+fn range_between_entries() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=4, 'a');
+    map.insert_range(8..=12, 'c');
+
+    assert_eq!(map.range(5..8).count(), 0);
+    assert_eq!(map.range(20..30).count(), 0);
+
+    let (start, end) = (10, 2);
+    assert_eq!(map.range(start..end).count(), 0);
+}
+
+#[test]
+fn remove_range_covering_whole_ranges() {
+    let mut map = RangedMap::new();
+    map.insert_range(2..=3, 'a');
+    map.insert(5, 'b');
+
+    assert!(map.remove_range(0..=10));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn remove_range_from_start_of_range() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=9, 1);
+
+    assert!(map.remove_range(0..=5));
+    assert_eq!(map.get(&6), Some(&1));
+    assert!(map.remove_range(0..=9));
+    assert!(!map.remove_range(0..=9));
+}
+
+#[test]
+fn update_range_fills_gaps_and_coalesces() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=2, 1);
+    map.insert_range(4..=5, 2);
+    map.insert_range(8..=9, 2);
+
+    map.update_range(Span::new(1, 8), |value| Some(value.map_or(2, |v| v + 1)));
+
+    assert_eq!(
+        map.entries,
+        vec![
+            (Single(0), 1),
+            (Range(Span::new(1, 3)), 2),
+            (Range(Span::new(4, 5)), 3),
+            (Range(Span::new(6, 7)), 2),
+            (Single(8), 3),
+            (Single(9), 2),
+        ]
+    );
+}
+
+#[test]
+fn update_range_removes_keys_mapped_to_none() {
+    let mut map = RangedMap::new();
+    map.insert_range(0..=9, 1);
+    map.insert_range(3..=4, 2);
+
+    map.update_range(Span::new(2, 6), |value| match value {
+        Some(&2) => None,
+        other => other.cloned(),
+    });
+
+    assert_eq!(
+        map.entries,
+        vec![(Range(Span::new(0, 2)), 1), (Range(Span::new(5, 9)), 1),]
+    );
+}
+
+#[test]
+fn unbounded_ranges() {
+    let mut map = RangedMap::new();
+    map.insert_range(.., 'a');
+    map.insert_range(..10u8, 'b');
+    map.remove_range(250..);
+
+    let entries: Vec<_> = map.range(5..).collect();
+    assert_eq!(entries, vec![(5..=9, &'b'), (10..=249, &'a')]);
+}
@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod tests;
+
+use crate::discrete::Discrete;
+use crate::set::RangedSet;
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, SeqAccess, Visitor};
+use serde::ser::{SerializeSeq, SerializeTuple, Serializer};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::{fmt, ops};
+
+/// Writes the set as a sequence of its ranges
+///
+/// Each range is written as a `[start, end]` pair. Human-readable
+/// formats like JSON write lone values as `[value]` to keep the output
+/// short, while compact formats always write both ends so every range
+/// has the same fixed-size layout.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::RangedSet;
+/// use serde_test::{Configure, Token, assert_ser_tokens};
+///
+/// let set: RangedSet<i32> = vec![1..=5, 7..=7].into_iter().collect();
+///
+/// // Written as [[1, 5], [7]]
+/// assert_ser_tokens(
+///     &set.readable(),
+///     &[
+///         Token::Seq { len: Some(2) },
+///         Token::Seq { len: Some(2) },
+///         Token::I32(1),
+///         Token::I32(5),
+///         Token::SeqEnd,
+///         Token::Seq { len: Some(1) },
+///         Token::I32(7),
+///         Token::SeqEnd,
+///         Token::SeqEnd,
+///     ],
+/// );
+/// ```
+impl<T: Discrete + Serialize> Serialize for RangedSet<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let human_readable = serializer.is_human_readable();
+
+        serializer.collect_seq(self.ranges().map(|range| Pair {
+            range,
+            human_readable,
+        }))
+    }
+}
+
+/// Reads a set from a sequence of ranges
+///
+/// This accepts what the `Serialize` implementation writes. The ranges
+/// don't need to be sorted and can overlap or touch each other; they
+/// are merged as they are read. A range that ends before it starts is
+/// an error.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::RangedSet;
+/// use serde_test::{Configure, Token, assert_de_tokens};
+///
+/// let set: RangedSet<i32> = vec![1..=5, 7..=7].into_iter().collect();
+///
+/// // Read from [[7], [3, 5], [1, 4]]
+/// assert_de_tokens(
+///     &set.readable(),
+///     &[
+///         Token::Seq { len: Some(3) },
+///         Token::Seq { len: Some(1) },
+///         Token::I32(7),
+///         Token::SeqEnd,
+///         Token::Seq { len: Some(2) },
+///         Token::I32(3),
+///         Token::I32(5),
+///         Token::SeqEnd,
+///         Token::Seq { len: Some(2) },
+///         Token::I32(1),
+///         Token::I32(4),
+///         Token::SeqEnd,
+///         Token::SeqEnd,
+///     ],
+/// );
+/// ```
+impl<'de, T: Discrete + Deserialize<'de>> Deserialize<'de> for RangedSet<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let human_readable = deserializer.is_human_readable();
+
+        deserializer.deserialize_seq(SetVisitor {
+            human_readable,
+            marker: PhantomData,
+        })
+    }
+}
+
+/// A single range of a set being written
+struct Pair<T> {
+    range: ops::RangeInclusive<T>,
+    human_readable: bool,
+}
+
+impl<T: Serialize + PartialEq> Serialize for Pair<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (start, end) = (self.range.start(), self.range.end());
+
+        if self.human_readable {
+            let single = start == end;
+            let mut seq = serializer.serialize_seq(Some(if single { 1 } else { 2 }))?;
+            seq.serialize_element(start)?;
+            if !single {
+                seq.serialize_element(end)?;
+            }
+            seq.end()
+        } else {
+            let mut tuple = serializer.serialize_tuple(2)?;
+            tuple.serialize_element(start)?;
+            tuple.serialize_element(end)?;
+            tuple.end()
+        }
+    }
+}
+
+struct SetVisitor<T> {
+    human_readable: bool,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: Discrete + Deserialize<'de>> Visitor<'de> for SetVisitor<T> {
+    type Value = RangedSet<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a sequence of ranges")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut set = RangedSet::new();
+
+        let seed = PairSeed {
+            human_readable: self.human_readable,
+            marker: PhantomData,
+        };
+        while let Some(range) = seq.next_element_seed(seed)? {
+            set.extend(Some(range));
+        }
+
+        Ok(set)
+    }
+}
+
+/// Reads a single range in the layout the format uses
+struct PairSeed<T> {
+    human_readable: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T> Clone for PairSeed<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PairSeed<T> {}
+
+impl<'de, T: Clone + Ord + Deserialize<'de>> DeserializeSeed<'de> for PairSeed<T> {
+    type Value = ops::RangeInclusive<T>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        if self.human_readable {
+            deserializer.deserialize_seq(PairVisitor(PhantomData))
+        } else {
+            deserializer.deserialize_tuple(2, PairVisitor(PhantomData))
+        }
+    }
+}
+
+struct PairVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Clone + Ord + Deserialize<'de>> Visitor<'de> for PairVisitor<T> {
+    type Value = ops::RangeInclusive<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a range as [start, end] or [value]")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let start: T = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let end = match seq.next_element()? {
+            Some(end) => end,
+            None => start.clone(),
+        };
+
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(3, &self));
+        }
+        if end < start {
+            return Err(de::Error::custom("range ends before it starts"));
+        }
+
+        Ok(start..=end)
+    }
+}
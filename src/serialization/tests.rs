@@ -0,0 +1,171 @@
+use crate::set::RangedSet;
+use serde_test::{
+    Compact, Configure, Readable, Token, assert_de_tokens, assert_de_tokens_error, assert_tokens,
+};
+
+#[test]
+fn readable_empty_set() {
+    let rs: RangedSet<i32> = RangedSet::new();
+
+    assert_tokens(
+        &rs.readable(),
+        &[Token::Seq { len: Some(0) }, Token::SeqEnd],
+    );
+}
+
+#[test]
+fn readable_normalizes_unsorted_and_overlapping_ranges() {
+    let rs: RangedSet<i32> = vec![1..=4, 10..=25].into_iter().collect();
+
+    assert_de_tokens(
+        &rs.readable(),
+        &[
+            Token::Seq { len: Some(5) },
+            Token::Seq { len: Some(2) },
+            Token::I32(10),
+            Token::I32(20),
+            Token::SeqEnd,
+            Token::Seq { len: Some(1) },
+            Token::I32(3),
+            Token::SeqEnd,
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::I32(2),
+            Token::SeqEnd,
+            Token::Seq { len: Some(2) },
+            Token::I32(15),
+            Token::I32(25),
+            Token::SeqEnd,
+            Token::Seq { len: Some(2) },
+            Token::I32(4),
+            Token::I32(4),
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn readable_accepts_single_as_pair() {
+    let rs: RangedSet<i32> = vec![7..=7].into_iter().collect();
+
+    assert_de_tokens(
+        &rs.readable(),
+        &[
+            Token::Seq { len: Some(1) },
+            Token::Seq { len: Some(2) },
+            Token::I32(7),
+            Token::I32(7),
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn readable_rejects_inverted_range() {
+    assert_de_tokens_error::<Readable<RangedSet<i32>>>(
+        &[
+            Token::Seq { len: Some(1) },
+            Token::Seq { len: Some(2) },
+            Token::I32(5),
+            Token::I32(1),
+            Token::SeqEnd,
+        ],
+        "range ends before it starts",
+    );
+}
+
+#[test]
+fn readable_rejects_wrong_lengths() {
+    assert_de_tokens_error::<Readable<RangedSet<i32>>>(
+        &[
+            Token::Seq { len: Some(1) },
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+        ],
+        "invalid length 0, expected a range as [start, end] or [value]",
+    );
+    assert_de_tokens_error::<Readable<RangedSet<i32>>>(
+        &[
+            Token::Seq { len: Some(1) },
+            Token::Seq { len: Some(3) },
+            Token::I32(1),
+            Token::I32(2),
+            Token::I32(3),
+            Token::SeqEnd,
+        ],
+        "invalid length 3, expected a range as [start, end] or [value]",
+    );
+    assert_de_tokens_error::<Readable<RangedSet<i32>>>(
+        &[Token::Seq { len: Some(1) }, Token::I32(1)],
+        "invalid type: integer `1`, expected a range as [start, end] or [value]",
+    );
+}
+
+#[test]
+fn bincode_round_trip() {
+    let rs: RangedSet<u64> = vec![0..=0, 5..=9, 1000..=u64::MAX].into_iter().collect();
+
+    let bytes = bincode::serialize(&rs).unwrap();
+    // A length prefix, then two fixed-width ends per range
+    assert_eq!(bytes.len(), 8 + 3 * 16);
+
+    let decoded: RangedSet<u64> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(decoded, rs);
+}
+
+#[test]
+fn readable_tokens() {
+    let rs: RangedSet<i32> = vec![1..=5, 7..=7].into_iter().collect();
+
+    assert_tokens(
+        &rs.readable(),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::I32(5),
+            Token::SeqEnd,
+            Token::Seq { len: Some(1) },
+            Token::I32(7),
+            Token::SeqEnd,
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn compact_tokens() {
+    let rs: RangedSet<i32> = vec![1..=5, 7..=7].into_iter().collect();
+
+    assert_tokens(
+        &rs.compact(),
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Tuple { len: 2 },
+            Token::I32(1),
+            Token::I32(5),
+            Token::TupleEnd,
+            Token::Tuple { len: 2 },
+            Token::I32(7),
+            Token::I32(7),
+            Token::TupleEnd,
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn compact_rejects_inverted_range() {
+    assert_de_tokens_error::<Compact<RangedSet<i32>>>(
+        &[
+            Token::Seq { len: Some(1) },
+            Token::Tuple { len: 2 },
+            Token::I32(5),
+            Token::I32(1),
+            Token::TupleEnd,
+        ],
+        "range ends before it starts",
+    );
+}
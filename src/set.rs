@@ -1,14 +1,23 @@
 #[cfg(test)]
 mod tests;
 
-use crate::element::Element;
+use crate::algebra::{Difference, Intersection, Spans, SymmetricDifference, Union};
+use crate::discrete::Discrete;
+use crate::element::{Element, SplitError};
+use crate::iter::{Gaps, IntoIter, Iter, Ranges};
+use crate::span::Span;
 use std::clone::Clone;
-use step::Step;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::{self, RangeBounds};
 
 /// A set that stores values in contiguous ranges
 ///
 /// `RangedSet` stores numeric values (or values that implement the
-/// [`Step`] trait) in ranges to conserve space.
+/// [`Discrete`] trait) in ranges to conserve space.
 ///
 /// # When is using `RangedSet` a good idea?
 ///
@@ -35,13 +44,20 @@ use step::Step;
 /// // two i32's.
 /// ```
 ///
-/// [`Step`]: https://docs.rs/step/0.1.0/step/
-#[derive(Debug, Default)]
-pub struct RangedSet<T: Step + Clone + Ord> {
+/// [`Discrete`]: trait.Discrete.html
+#[derive(Clone, Default)]
+pub struct RangedSet<T: Discrete> {
     ranges: Vec<Element<T>>,
+    // The running total of values in the elements up to and including
+    // the element at the same index in `ranges`, counted from `base`
+    // rather than zero.
+    counts: Vec<u128>,
+    // Taking values off the front of the set raises this instead of
+    // lowering every total after them.
+    base: u128,
 }
 
-impl<T: Step + Clone + Ord> RangedSet<T> {
+impl<T: Discrete> RangedSet<T> {
     /// Returns a new empty set
     ///
     /// # Example
@@ -51,7 +67,135 @@ impl<T: Step + Clone + Ord> RangedSet<T> {
     /// let mut set: RangedSet<i32> = RangedSet::new();
     /// ```
     pub fn new() -> RangedSet<T> {
-        RangedSet { ranges: Vec::new() }
+        RangedSet {
+            ranges: Vec::new(),
+            counts: Vec::new(),
+            base: 0,
+        }
+    }
+
+    /// Returns `true` if the set contains no values
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// assert_eq!(set.is_empty(), true);
+    ///
+    /// set.insert(1);
+    /// assert_eq!(set.is_empty(), false);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the number of values in the set
+    ///
+    /// The count is kept up to date as the set changes, so this takes
+    /// constant time. It's returned as a `u128` since a set covering
+    /// the whole domain of a 64-bit type has more values than a `u64`
+    /// (or a `usize`) can count. Only a set of more than `u128::MAX`
+    /// `u128`s or `i128`s is too big for that, and its length saturates
+    /// at `u128::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0..=9);
+    /// set.insert(20);
+    /// assert_eq!(set.len(), 11);
+    ///
+    /// let mut full = RangedSet::new();
+    /// full.insert_range(0..=u64::MAX);
+    /// assert_eq!(full.len(), 1 << 64);
+    /// ```
+    pub fn len(&self) -> u128 {
+        self.count_before(self.ranges.len())
+    }
+
+    /// Returns the number of contiguous ranges stored in the set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0..=9);
+    /// set.insert(20);
+    /// assert_eq!(set.range_count(), 2);
+    /// ```
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns the number of values in the set that are less than a
+    /// value
+    ///
+    /// A running total of the values in each range is kept as the set
+    /// changes, so this only needs a binary search over the ranges.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(10..=19);
+    /// set.insert_range(30..=39);
+    ///
+    /// assert_eq!(set.rank(&0), 0);
+    /// assert_eq!(set.rank(&15), 5);
+    /// assert_eq!(set.rank(&25), 10);
+    /// assert_eq!(set.rank(&35), 15);
+    /// ```
+    pub fn rank(&self, value: &T) -> u128 {
+        match self.find_index_for(value) {
+            Ok(index) => self
+                .count_before(index)
+                .saturating_add(self.ranges[index].start().distance(value)),
+            Err(index) => self.count_before(index),
+        }
+    }
+
+    /// Returns the value at a position in the sorted values of the set
+    ///
+    /// Positions start from zero, so `select(0)` returns the smallest
+    /// value in the set. `None` is returned if there aren't enough
+    /// values in the set. Like [`rank`], this only needs a binary search
+    /// over the ranges.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(10..=19);
+    /// set.insert_range(30..=39);
+    ///
+    /// assert_eq!(set.select(0), Some(10));
+    /// assert_eq!(set.select(15), Some(35));
+    /// assert_eq!(set.select(20), None);
+    /// ```
+    ///
+    /// [`rank`]: #method.rank
+    pub fn select(&self, position: u128) -> Option<T> {
+        // The element holding the value is the first one whose running
+        // total is past the position.
+        let total = self.base.checked_add(position)?;
+        let index = match self.counts.binary_search(&total) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        };
+
+        let element = self.ranges.get(index)?;
+        element.start().forward(position - self.count_before(index))
     }
 
     /// Returns `true` if the set contains a value.
@@ -73,10 +217,25 @@ impl<T: Step + Clone + Ord> RangedSet<T> {
         self.find_index_for(value).is_ok()
     }
 
-    /// Adds a value to the set
+    /// Returns the smallest value in the set
     ///
-    /// If the set did not have this value present, `true` is returned.
-    /// If the set did have this value present, `false` is returned.
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// assert_eq!(set.first(), None);
+    ///
+    /// set.insert_range(3..=5);
+    /// set.insert(9);
+    /// assert_eq!(set.first(), Some(&3));
+    /// ```
+    pub fn first(&self) -> Option<&T> {
+        self.ranges.first().map(Element::start)
+    }
+
+    /// Returns the largest value in the set
     ///
     /// # Example
     ///
@@ -84,74 +243,128 @@ impl<T: Step + Clone + Ord> RangedSet<T> {
     /// use ranged_set::RangedSet;
     ///
     /// let mut set = RangedSet::new();
-    /// assert_eq!(set.insert(1), true);
-    /// assert_eq!(set.insert(1), false);
+    /// assert_eq!(set.last(), None);
+    ///
+    /// set.insert_range(3..=5);
+    /// set.insert(9);
+    /// assert_eq!(set.last(), Some(&9));
     /// ```
-    pub fn insert(&mut self, value: T) -> bool {
-        enum Operation<T> {
-            InsertSingle(usize, T),
-            TwoWayMerge(usize, T),
-            ThreeWayMerge(usize, usize, T),
+    pub fn last(&self) -> Option<&T> {
+        self.ranges.last().map(Element::end)
+    }
+
+    /// Removes and returns the smallest value in the set
+    ///
+    /// The first range is shrunk in place, so this doesn't need to
+    /// search the set like [`take`] does, and the running totals kept
+    /// for [`rank`] and [`select`] don't need updating. It only takes
+    /// longer when the first range is used up and the rest of the
+    /// ranges move down.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(3..=4);
+    /// set.insert(9);
+    ///
+    /// assert_eq!(set.pop_first(), Some(3));
+    /// assert_eq!(set.pop_first(), Some(4));
+    /// assert_eq!(set.pop_first(), Some(9));
+    /// assert_eq!(set.pop_first(), None);
+    /// ```
+    ///
+    /// [`take`]: #method.take
+    /// [`rank`]: #method.rank
+    /// [`select`]: #method.select
+    pub fn pop_first(&mut self) -> Option<T> {
+        let first = self.ranges.first()?;
+        let value = first.start().clone();
+
+        if first.start() == first.end() {
+            self.ranges.remove(0);
+            self.counts.remove(0);
+        } else {
+            let start = value.successor()?;
+            let end = first.end().clone();
+            self.ranges[0] = Element::from_bounds(start, end);
         }
 
-        let index = match self.find_index_for(&value) {
-            // The value is already contained in the element at the
-            // index returned in the Ok() value, so nothing needs
-            // doing.
-            Ok(_) => return false,
+        // Every total after the value now has one value less in front of
+        // it, unless the totals saturated and have to be worked out again.
+        match self.counts.last() {
+            Some(&u128::MAX) => self.recount_from(0),
+            _ => self.base += 1,
+        }
 
-            // The value wasn't found, so the index contained in the
-            // Err() value is where to insert it to maintain sort
-            // order. The value needs to be added to the list of
-            // elements, either as a single value or by merging with
-            // another element.
-            Err(index) => index,
-        };
+        Some(value)
+    }
 
-        let before = match index.checked_sub(1).and_then(|i| self.ranges.get(i)) {
-            Some(b) => b.adjacent_to(&value),
-            None => false,
-        };
-        let after = match self.ranges.get(index) {
-            Some(a) => a.adjacent_to(&value),
-            None => false,
-        };
+    /// Removes and returns the largest value in the set
+    ///
+    /// The last range is shrunk in place, so this doesn't need to
+    /// search the set like [`take`] does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert(3);
+    /// set.insert_range(8..=9);
+    ///
+    /// assert_eq!(set.pop_last(), Some(9));
+    /// assert_eq!(set.pop_last(), Some(8));
+    /// assert_eq!(set.pop_last(), Some(3));
+    /// assert_eq!(set.pop_last(), None);
+    /// ```
+    ///
+    /// [`take`]: #method.take
+    pub fn pop_last(&mut self) -> Option<T> {
+        let last = self.ranges.len().checked_sub(1)?;
+        let element = &self.ranges[last];
+        let value = element.end().clone();
 
-        let operation = match (before, after) {
-            (false, false) => Operation::InsertSingle(index, value),
-            (true, false) => Operation::TwoWayMerge(index - 1, value),
-            (false, true) => Operation::TwoWayMerge(index, value),
-            (true, true) => Operation::ThreeWayMerge(index - 1, index, value),
-        };
+        if element.start() == element.end() {
+            self.ranges.pop();
+        } else {
+            let start = element.start().clone();
+            let end = value.predecessor()?;
+            self.ranges[last] = Element::from_bounds(start, end);
+        }
 
-        match operation {
-            Operation::InsertSingle(index, value) => {
-                self.ranges.insert(index, Element::Single(value));
-                true
-            }
-            Operation::TwoWayMerge(index, value) => {
-                let existing = self.ranges[index].clone();
-                let merged = existing.merge(value);
+        self.recount_from(last);
+        Some(value)
+    }
 
-                self.ranges.push(merged);
-                self.ranges.swap_remove(index);
-                true
-            }
-            Operation::ThreeWayMerge(index_before, index_after, value) => {
-                let before = self.ranges[index_before].clone();
-                let after = self.ranges[index_after].clone();
-                let merged_before = before.merge(value);
-                let merged = merged_before.merge(after);
-
-                self.ranges.push(merged);
-                self.ranges.swap_remove(index_before);
-                self.ranges.remove(index_after);
-                true
-            }
+    /// Returns the smallest value in the set that is greater than or
+    /// equal to a value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(3..=5);
+    /// set.insert(9);
+    ///
+    /// assert_eq!(set.ceiling(&4), Some(4));
+    /// assert_eq!(set.ceiling(&6), Some(9));
+    /// assert_eq!(set.ceiling(&10), None);
+    /// ```
+    pub fn ceiling(&self, value: &T) -> Option<T> {
+        match self.find_index_for(value) {
+            Ok(_) => Some(value.clone()),
+            Err(index) => self.ranges.get(index).map(|e| e.start().clone()),
         }
     }
 
-    /// Removes and returns a value from the set
+    /// Returns the largest value in the set that is less than or equal
+    /// to a value
     ///
     /// # Example
     ///
@@ -159,62 +372,112 @@ impl<T: Step + Clone + Ord> RangedSet<T> {
     /// use ranged_set::RangedSet;
     ///
     /// let mut set = RangedSet::new();
-    /// set.insert(0);
-    /// set.insert(1);
-    /// set.insert(2);
+    /// set.insert_range(3..=5);
+    /// set.insert(9);
     ///
-    /// assert_eq!(set.take(&0), Some(0));
-    /// assert_eq!(set.take(&5), None);
+    /// assert_eq!(set.floor(&4), Some(4));
+    /// assert_eq!(set.floor(&8), Some(5));
+    /// assert_eq!(set.floor(&2), None);
     /// ```
-    pub fn take(&mut self, value: &T) -> Option<T> {
-        enum Operation<T> {
-            Remove(usize),
-            Split(usize, T),
+    pub fn floor(&self, value: &T) -> Option<T> {
+        match self.find_index_for(value) {
+            Ok(_) => Some(value.clone()),
+            Err(index) => index
+                .checked_sub(1)
+                .map(|before| self.ranges[before].end().clone()),
         }
+    }
 
-        let index = self.find_index_for(value).ok()?;
+    /// Returns the smallest value in the set that is strictly greater
+    /// than a value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(3..=5);
+    /// set.insert(9);
+    ///
+    /// assert_eq!(set.successor(&4), Some(5));
+    /// assert_eq!(set.successor(&5), Some(9));
+    /// assert_eq!(set.successor(&9), None);
+    /// ```
+    pub fn successor(&self, value: &T) -> Option<T> {
+        value.successor().and_then(|n| self.ceiling(&n))
+    }
 
-        let operation = match self.ranges[index] {
-            Element::Single(_) => Operation::Remove(index),
-            Element::Range(_) => Operation::Split(index, value.clone()),
-        };
+    /// Returns the largest value in the set that is strictly less than
+    /// a value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(3..=5);
+    /// set.insert(9);
+    ///
+    /// assert_eq!(set.predecessor(&4), Some(3));
+    /// assert_eq!(set.predecessor(&9), Some(5));
+    /// assert_eq!(set.predecessor(&3), None);
+    /// ```
+    pub fn predecessor(&self, value: &T) -> Option<T> {
+        value.predecessor().and_then(|p| self.floor(&p))
+    }
 
-        match operation {
-            Operation::Remove(index) => match self.ranges.remove(index) {
-                Element::Single(v) => Some(v),
-                Element::Range(_) => unreachable!(),
-            },
-            Operation::Split(index, value) => match self.ranges[index].clone() {
-                e @ Element::Range(_) => {
-                    let (b, v, a) = e.split(&value);
-                    match (b, a) {
-                        (Some(b), Some(a)) => {
-                            self.ranges.push(a);
-                            self.ranges.swap_remove(index);
-                            self.ranges.insert(index, b);
-                        }
-                        (None, Some(a)) => {
-                            self.ranges.push(a);
-                            self.ranges.swap_remove(index);
-                        }
-                        (Some(b), None) => {
-                            self.ranges.push(b);
-                            self.ranges.swap_remove(index);
-                        }
-                        (None, None) => unreachable!(),
-                    }
-
-                    Some(v)
-                }
-                _ => unreachable!(),
-            },
+    /// Returns the smallest value greater than or equal to a value that
+    /// isn't in the set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(3..=5);
+    /// set.insert_range(250u8..=255);
+    ///
+    /// assert_eq!(set.next_missing(&1), Some(1));
+    /// assert_eq!(set.next_missing(&4), Some(6));
+    /// assert_eq!(set.next_missing(&252), None);
+    /// ```
+    pub fn next_missing(&self, value: &T) -> Option<T> {
+        match self.find_index_for(value) {
+            Ok(index) => self.ranges[index].end().successor(),
+            Err(_) => Some(value.clone()),
         }
     }
 
-    /// Removes a value from the set
+    /// Returns the largest value less than or equal to a value that
+    /// isn't in the set
     ///
-    /// Removes a value from the set. Returns `true` if the value was
-    /// present in the set.
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0u8..=5);
+    /// set.insert(9);
+    ///
+    /// assert_eq!(set.prev_missing(&7), Some(7));
+    /// assert_eq!(set.prev_missing(&9), Some(8));
+    /// assert_eq!(set.prev_missing(&4), None);
+    /// ```
+    pub fn prev_missing(&self, value: &T) -> Option<T> {
+        match self.find_index_for(value) {
+            Ok(index) => self.ranges[index].start().predecessor(),
+            Err(_) => Some(value.clone()),
+        }
+    }
+
+    /// Returns an iterator over the values in the set
+    ///
+    /// The values are produced in ascending order by stepping through
+    /// each of the stored ranges.
     ///
     /// # Example
     ///
@@ -222,31 +485,1031 @@ impl<T: Step + Clone + Ord> RangedSet<T> {
     /// use ranged_set::RangedSet;
     ///
     /// let mut set = RangedSet::new();
-    /// set.insert(0);
-    /// set.insert(1);
-    /// set.insert(2);
+    /// set.insert_range(0..=2);
+    /// set.insert(5);
     ///
-    /// assert_eq!(set.remove(&0), true);
-    /// assert_eq!(set.remove(&5), false);
+    /// let values: Vec<_> = set.iter().collect();
+    /// assert_eq!(values, vec![0, 1, 2, 5]);
+    ///
+    /// let reversed: Vec<_> = set.iter().rev().collect();
+    /// assert_eq!(reversed, vec![5, 2, 1, 0]);
     /// ```
-    pub fn remove(&mut self, value: &T) -> bool {
-        self.take(value).is_some()
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(&self.ranges)
     }
 
-    fn find_index_for(&self, value: &T) -> Result<usize, usize> {
-        use std::cmp::Ordering;
-
-        self.ranges.binary_search_by(|member| match member {
-            Element::Single(s) => s.cmp(value),
-            Element::Range(r) => {
-                if r.end < *value {
-                    Ordering::Less
-                } else if *value < r.start {
-                    Ordering::Greater
-                } else {
-                    Ordering::Equal
-                }
+    /// Returns an iterator over the values in the set that are inside a
+    /// range
+    ///
+    /// Like [`iter`], the values are produced in ascending order and
+    /// the iterator can be reversed. Empty and inverted ranges produce
+    /// nothing, and an unbounded end reaches the smallest or largest
+    /// value of the type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0..=9);
+    /// set.insert_range(20..=29);
+    ///
+    /// let values: Vec<_> = set.range(7..22).collect();
+    /// assert_eq!(values, vec![7, 8, 9, 20, 21]);
+    ///
+    /// let values: Vec<_> = set.range(25..).rev().collect();
+    /// assert_eq!(values, vec![29, 28, 27, 26, 25]);
+    /// ```
+    ///
+    /// [`iter`]: #method.iter
+    pub fn range<R>(&self, range: R) -> Iter<'_, T>
+    where
+        R: RangeBounds<T>,
+    {
+        match Span::from_bounds(&range) {
+            Some(range) => {
+                let overlapping = self.overlapping(&range);
+                Iter::within(&self.ranges[overlapping], range)
+            }
+            None => Iter::new(&[]),
+        }
+    }
+
+    /// Returns an iterator over the contiguous ranges of values in the
+    /// set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0..=2);
+    /// set.insert(5);
+    ///
+    /// let ranges: Vec<_> = set.ranges().collect();
+    /// assert_eq!(ranges, vec![0..=2, 5..=5]);
+    /// ```
+    pub fn ranges(&self) -> Ranges<'_, T> {
+        Ranges::new(&self.ranges)
+    }
+
+    /// Returns an iterator over the contiguous ranges of values in the
+    /// set that are inside a range
+    ///
+    /// Ranges that are only partly inside the range are clipped to it.
+    /// Empty and inverted ranges produce nothing, and an unbounded end
+    /// reaches the smallest or largest value of the type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0..=9);
+    /// set.insert(15);
+    /// set.insert_range(20..=29);
+    ///
+    /// let ranges: Vec<_> = set.ranges_in(5..25).collect();
+    /// assert_eq!(ranges, vec![5..=9, 15..=15, 20..=24]);
+    /// ```
+    pub fn ranges_in<R>(&self, range: R) -> Ranges<'_, T>
+    where
+        R: RangeBounds<T>,
+    {
+        match Span::from_bounds(&range) {
+            Some(range) => {
+                let overlapping = self.overlapping(&range);
+                Ranges::within(&self.ranges[overlapping], range)
             }
-        })
+            None => Ranges::new(&[]),
+        }
+    }
+
+    /// Returns an iterator over the ranges of values missing between
+    /// the ranges in the set
+    ///
+    /// Only the holes between stored ranges are produced; the values
+    /// below the first range and above the last one aren't considered
+    /// missing. Use [`gaps_within`] to include those.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0..=2);
+    /// set.insert_range(5..=6);
+    /// set.insert(9);
+    ///
+    /// let gaps: Vec<_> = set.gaps().collect();
+    /// assert_eq!(gaps, vec![3..=4, 7..=8]);
+    /// ```
+    ///
+    /// [`gaps_within`]: #method.gaps_within
+    pub fn gaps(&self) -> Gaps<'_, T> {
+        Gaps::between(&self.ranges)
+    }
+
+    /// Returns an iterator over the ranges of values in a window that
+    /// are missing from the set
+    ///
+    /// Unlike [`gaps`], this includes the holes between the start of the
+    /// window and the first range and between the last range and the
+    /// end of the window.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(3..=5);
+    /// set.insert(8);
+    ///
+    /// let gaps: Vec<_> = set.gaps_within(0..=10).collect();
+    /// assert_eq!(gaps, vec![0..=2, 6..=7, 9..=10]);
+    ///
+    /// let gaps: Vec<_> = set.gaps_within(4..8).collect();
+    /// assert_eq!(gaps, vec![6..=7]);
+    /// ```
+    ///
+    /// [`gaps`]: #method.gaps
+    pub fn gaps_within<R>(&self, window: R) -> Gaps<'_, T>
+    where
+        R: RangeBounds<T>,
+    {
+        let window = match Span::from_bounds(&window) {
+            Some(window) => window,
+            None => return Gaps::within(&[], None),
+        };
+
+        let overlapping = self.overlapping(&window);
+        Gaps::within(&self.ranges[overlapping], Some(window))
+    }
+
+    /// Adds a value to the set
+    ///
+    /// If the set did not have this value present, `true` is returned.
+    /// If the set did have this value present, `false` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// assert_eq!(set.insert(1), true);
+    /// assert_eq!(set.insert(1), false);
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool {
+        let index = match self.find_index_for(&value) {
+            // The value is already contained in the element at the
+            // index returned in the Ok() value, so nothing needs
+            // doing.
+            Ok(_) => return false,
+
+            // The value wasn't found, so the index contained in the
+            // Err() value is where to insert it to maintain sort
+            // order. The value needs to be added to the list of
+            // elements, either as a single value or by merging with
+            // another element.
+            Err(index) => index,
+        };
+
+        // Merge the value into the elements either side of it where they
+        // end or start right next to it, replacing them with the result.
+        let mut merged = Element::Single(value);
+        let mut first = index;
+        if let Some(before) = index.checked_sub(1) {
+            if let Some(m) = self.ranges[before].clone().merge(merged.clone()) {
+                merged = m;
+                first = before;
+            }
+        }
+
+        let mut last = index;
+        if let Some(after) = self.ranges.get(index) {
+            if let Some(m) = merged.clone().merge(after.clone()) {
+                merged = m;
+                last = index + 1;
+            }
+        }
+
+        self.ranges.splice(first..last, Some(merged));
+        self.recount_from(first);
+        true
+    }
+
+    /// Adds every value in a range to the set
+    ///
+    /// The range is merged with every element it overlaps or is
+    /// adjacent to in a single pass, so inserting a large contiguous
+    /// block of values is much cheaper than inserting them one at a
+    /// time. Empty and inverted ranges leave the set unchanged, and an
+    /// unbounded end reaches the smallest or largest value of the type.
+    ///
+    /// If the set did not have every value in the range present, `true`
+    /// is returned. If the set already contained the whole range,
+    /// `false` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert(3);
+    ///
+    /// assert_eq!(set.insert_range(0..=5), true);
+    /// assert_eq!(set.insert_range(1..4), false);
+    /// assert_eq!(set.contains(&5), true);
+    ///
+    /// set.insert_range(250u8..);
+    /// assert_eq!(set.contains(&255), true);
+    /// ```
+    pub fn insert_range<R>(&mut self, range: R) -> bool
+    where
+        R: RangeBounds<T>,
+    {
+        match Span::from_bounds(&range) {
+            Some(range) => self.insert_span(range),
+            None => false,
+        }
+    }
+
+    fn insert_span(&mut self, range: Span<T>) -> bool {
+        // The first element affected is the one containing the start of
+        // the range or, failing that, the one ending right before it.
+        let first = match self.find_index_for(&range.start) {
+            Ok(index) => index,
+            Err(index) => match index.checked_sub(1) {
+                Some(before) if self.ranges[before].adjacent_to(&range.start) => before,
+                _ => index,
+            },
+        };
+
+        // The last element affected (exclusive) is found the same way
+        // from the end of the range.
+        let last = match self.find_index_for(&range.end) {
+            Ok(index) => index + 1,
+            Err(index) => match self.ranges.get(index) {
+                Some(after) if after.adjacent_to(&range.end) => index + 1,
+                _ => index,
+            },
+        };
+
+        if last - first == 1 {
+            let existing = &self.ranges[first];
+            if *existing.start() <= range.start && range.end <= *existing.end() {
+                return false;
+            }
+        }
+
+        let merged = self.ranges[first..last]
+            .iter()
+            .cloned()
+            .try_fold(Element::from_bounds(range.start, range.end), |merged, e| {
+                merged.merge(e)
+            });
+        let merged = match merged {
+            Some(merged) => merged,
+            None => return false,
+        };
+
+        self.ranges.splice(first..last, Some(merged));
+        self.recount_from(first);
+        true
+    }
+
+    /// Removes and returns a value from the set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert(0);
+    /// set.insert(1);
+    /// set.insert(2);
+    ///
+    /// assert_eq!(set.take(&0), Some(0));
+    /// assert_eq!(set.take(&5), None);
+    /// ```
+    pub fn take(&mut self, value: &T) -> Option<T> {
+        let index = self.find_index_for(value).ok()?;
+
+        let (prev, taken, next) = match self.ranges[index].split(value) {
+            Ok(parts) => parts,
+            Err(SplitError::Single) => (None, self.ranges[index].start().clone(), None),
+            Err(SplitError::OutOfRange) => return None,
+        };
+
+        self.ranges
+            .splice(index..=index, prev.into_iter().chain(next));
+        self.recount_from(index);
+        Some(taken)
+    }
+
+    /// Removes a value from the set
+    ///
+    /// Removes a value from the set. Returns `true` if the value was
+    /// present in the set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert(0);
+    /// set.insert(1);
+    /// set.insert(2);
+    ///
+    /// assert_eq!(set.remove(&0), true);
+    /// assert_eq!(set.remove(&5), false);
+    /// ```
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    /// Removes every value in a range from the set
+    ///
+    /// Elements that lie entirely inside the range are dropped and the
+    /// elements at either end of the range are trimmed (or split in two
+    /// if the range lands in the middle of one). Returns the number of
+    /// values that were removed. Empty and inverted ranges leave the
+    /// set unchanged, and an unbounded end reaches the smallest or
+    /// largest value of the type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0..=20);
+    ///
+    /// assert_eq!(set.remove_range(5..10), 5);
+    /// assert_eq!(set.remove_range(0..=7), 5);
+    /// assert_eq!(set.contains(&10), true);
+    ///
+    /// assert_eq!(set.remove_range(..=15), 6);
+    /// assert_eq!(set.first(), Some(&16));
+    /// ```
+    pub fn remove_range<R>(&mut self, range: R) -> u128
+    where
+        R: RangeBounds<T>,
+    {
+        let range = match Span::from_bounds(&range) {
+            Some(range) => range,
+            None => return 0,
+        };
+
+        let overlapping = self.overlapping(&range);
+        let first = overlapping.start;
+
+        // Only the elements at either end can have values left over
+        // after being split, so there will be at most two of these.
+        let mut remaining = Vec::new();
+        let removed = self
+            .ranges
+            .drain(overlapping)
+            .filter_map(|e| e.split_range(&range))
+            .map(|(prev, removed, next)| {
+                remaining.extend(prev);
+                remaining.extend(next);
+
+                removed.len()
+            })
+            .fold(0, u128::saturating_add);
+
+        for (offset, element) in remaining.into_iter().enumerate() {
+            self.ranges.insert(first + offset, element);
+        }
+
+        self.recount_from(first);
+        removed
+    }
+
+    /// Returns `true` if the set contains every value in a range
+    ///
+    /// Empty and inverted ranges are contained by every set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0..=10);
+    ///
+    /// assert_eq!(set.contains_range(2..=8), true);
+    /// assert_eq!(set.contains_range(8..12), false);
+    /// ```
+    pub fn contains_range<R>(&self, range: R) -> bool
+    where
+        R: RangeBounds<T>,
+    {
+        let range = match Span::from_bounds(&range) {
+            Some(range) => range,
+            None => return true,
+        };
+
+        match self.find_index_for(&range.start) {
+            Ok(index) => range.end <= *self.ranges[index].end(),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns `true` if the set contains any value in a range
+    ///
+    /// Empty and inverted ranges don't intersect any set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0..=10);
+    ///
+    /// assert_eq!(set.intersects_range(8..12), true);
+    /// assert_eq!(set.intersects_range(11..=20), false);
+    /// ```
+    pub fn intersects_range<R>(&self, range: R) -> bool
+    where
+        R: RangeBounds<T>,
+    {
+        let range = match Span::from_bounds(&range) {
+            Some(range) => range,
+            None => return false,
+        };
+
+        match self.find_index_for(&range.start) {
+            Ok(_) => true,
+            Err(index) => match self.ranges.get(index) {
+                Some(after) => *after.start() <= range.end,
+                None => false,
+            },
+        }
+    }
+
+    /// Returns `true` if every value in the set is also in another
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut granted = RangedSet::new();
+    /// granted.insert_range(0..=100);
+    ///
+    /// let mut requested = RangedSet::new();
+    /// requested.insert_range(10..=20);
+    /// requested.insert(50);
+    ///
+    /// assert_eq!(requested.is_subset(&granted), true);
+    ///
+    /// requested.insert(101);
+    /// assert_eq!(requested.is_subset(&granted), false);
+    /// ```
+    pub fn is_subset(&self, other: &RangedSet<T>) -> bool {
+        let mut others = other.ranges.iter().peekable();
+
+        for element in &self.ranges {
+            // Skip the elements in the other set that end before this
+            // element starts, then the next one has to cover it.
+            while others.peek().map_or(false, |o| o.end() < element.start()) {
+                others.next();
+            }
+
+            match others.peek() {
+                Some(o) if o.start() <= element.start() && element.end() <= o.end() => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if every value in another set is also in this one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut granted = RangedSet::new();
+    /// granted.insert_range(0..=100);
+    ///
+    /// let mut requested = RangedSet::new();
+    /// requested.insert_range(10..=20);
+    ///
+    /// assert_eq!(granted.is_superset(&requested), true);
+    /// ```
+    pub fn is_superset(&self, other: &RangedSet<T>) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if the set has no values in common with another
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut a = RangedSet::new();
+    /// a.insert_range(0..=9);
+    ///
+    /// let mut b = RangedSet::new();
+    /// b.insert_range(10..=19);
+    ///
+    /// assert_eq!(a.is_disjoint(&b), true);
+    ///
+    /// b.insert(5);
+    /// assert_eq!(a.is_disjoint(&b), false);
+    /// ```
+    pub fn is_disjoint(&self, other: &RangedSet<T>) -> bool {
+        let mut a = self.ranges.iter().peekable();
+        let mut b = other.ranges.iter().peekable();
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            if x.end() < y.start() {
+                a.next();
+            } else if y.end() < x.start() {
+                b.next();
+            } else {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns the values in either set as ranges
+    ///
+    /// The ranges are produced lazily, in ascending order, by a single
+    /// sweep over both sets. Ranges that overlap or are adjacent are
+    /// merged, so the output is the same as the ranges of the set
+    /// holding the union.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut a = RangedSet::new();
+    /// a.insert_range(0..=3);
+    /// a.insert(9);
+    ///
+    /// let mut b = RangedSet::new();
+    /// b.insert_range(4..=6);
+    ///
+    /// let union: Vec<_> = a.union(&b).collect();
+    /// assert_eq!(union, vec![0..=6, 9..=9]);
+    /// ```
+    pub fn union<'a>(&'a self, other: &'a RangedSet<T>) -> Union<'a, T> {
+        Union::new(self.spans(), other.spans())
+    }
+
+    /// Returns the values in both sets as ranges
+    ///
+    /// The ranges are produced lazily, in ascending order, by a single
+    /// sweep over both sets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut a = RangedSet::new();
+    /// a.insert_range(0..=5);
+    /// a.insert_range(8..=12);
+    ///
+    /// let mut b = RangedSet::new();
+    /// b.insert_range(3..=9);
+    ///
+    /// let intersection: Vec<_> = a.intersection(&b).collect();
+    /// assert_eq!(intersection, vec![3..=5, 8..=9]);
+    /// ```
+    pub fn intersection<'a>(&'a self, other: &'a RangedSet<T>) -> Intersection<'a, T> {
+        Intersection::new(self.spans(), other.spans())
+    }
+
+    /// Returns the values in this set but not the other as ranges
+    ///
+    /// The ranges are produced lazily, in ascending order, by a single
+    /// sweep over both sets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut a = RangedSet::new();
+    /// a.insert_range(0..=10);
+    ///
+    /// let mut b = RangedSet::new();
+    /// b.insert_range(3..=4);
+    /// b.insert(10);
+    ///
+    /// let difference: Vec<_> = a.difference(&b).collect();
+    /// assert_eq!(difference, vec![0..=2, 5..=9]);
+    /// ```
+    pub fn difference<'a>(&'a self, other: &'a RangedSet<T>) -> Difference<'a, T> {
+        Difference::new(self.spans(), other.spans())
+    }
+
+    /// Returns the values in exactly one of the sets as ranges
+    ///
+    /// The ranges are produced lazily, in ascending order, by a single
+    /// sweep over both sets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut a = RangedSet::new();
+    /// a.insert_range(0..=5);
+    ///
+    /// let mut b = RangedSet::new();
+    /// b.insert_range(3..=8);
+    ///
+    /// let symmetric_difference: Vec<_> = a.symmetric_difference(&b).collect();
+    /// assert_eq!(symmetric_difference, vec![0..=2, 6..=8]);
+    /// ```
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a RangedSet<T>,
+    ) -> SymmetricDifference<'a, T> {
+        SymmetricDifference::new(self.spans(), other.spans())
+    }
+
+    /// Adds every value in another set to this one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut a = RangedSet::new();
+    /// a.insert_range(0..=3);
+    ///
+    /// let mut b = RangedSet::new();
+    /// b.insert_range(4..=6);
+    ///
+    /// a.union_with(&b);
+    /// assert_eq!(a.contains(&5), true);
+    /// ```
+    pub fn union_with(&mut self, other: &RangedSet<T>) {
+        *self = RangedSet::from_canonical_ranges(self.union(other));
+    }
+
+    /// Removes every value from this set that isn't in another
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut a = RangedSet::new();
+    /// a.insert_range(0..=5);
+    ///
+    /// let mut b = RangedSet::new();
+    /// b.insert_range(3..=9);
+    ///
+    /// a.intersection_with(&b);
+    /// assert_eq!(a.contains(&2), false);
+    /// assert_eq!(a.contains(&3), true);
+    /// ```
+    pub fn intersection_with(&mut self, other: &RangedSet<T>) {
+        *self = RangedSet::from_canonical_ranges(self.intersection(other));
+    }
+
+    /// Removes every value in another set from this one
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut a = RangedSet::new();
+    /// a.insert_range(0..=5);
+    ///
+    /// let mut b = RangedSet::new();
+    /// b.insert_range(3..=9);
+    ///
+    /// a.difference_with(&b);
+    /// assert_eq!(a.contains(&2), true);
+    /// assert_eq!(a.contains(&3), false);
+    /// ```
+    pub fn difference_with(&mut self, other: &RangedSet<T>) {
+        *self = RangedSet::from_canonical_ranges(self.difference(other));
+    }
+
+    /// Keeps only the values that are in exactly one of the sets
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut a = RangedSet::new();
+    /// a.insert_range(0..=5);
+    ///
+    /// let mut b = RangedSet::new();
+    /// b.insert_range(3..=9);
+    ///
+    /// a.symmetric_difference_with(&b);
+    /// assert_eq!(a.contains(&2), true);
+    /// assert_eq!(a.contains(&3), false);
+    /// assert_eq!(a.contains(&9), true);
+    /// ```
+    pub fn symmetric_difference_with(&mut self, other: &RangedSet<T>) {
+        *self = RangedSet::from_canonical_ranges(self.symmetric_difference(other));
+    }
+
+    /// Returns a set of every value of the type not in this set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(0u8..=9);
+    /// set.insert(200);
+    ///
+    /// let complement = set.complement();
+    /// let ranges: Vec<_> = complement.ranges().collect();
+    /// assert_eq!(ranges, vec![10..=199, 201..=255]);
+    /// ```
+    pub fn complement(&self) -> RangedSet<T> {
+        self.complement_within(..)
+    }
+
+    /// Returns a set of every value in a range not in this set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let mut set = RangedSet::new();
+    /// set.insert_range(3..=5);
+    ///
+    /// let complement = set.complement_within(0..10);
+    /// let ranges: Vec<_> = complement.ranges().collect();
+    /// assert_eq!(ranges, vec![0..=2, 6..=9]);
+    /// ```
+    pub fn complement_within<R>(&self, range: R) -> RangedSet<T>
+    where
+        R: RangeBounds<T>,
+    {
+        RangedSet::from_canonical_ranges(self.gaps_within(range))
+    }
+
+    /// Returns the spans of the set for the sweeps in `algebra`
+    pub(crate) fn spans(&self) -> Spans<'_, T> {
+        Spans::Elements(self.ranges.iter())
+    }
+
+    /// Builds a set from ranges that are already sorted and neither
+    /// overlap nor touch each other
+    pub(crate) fn from_canonical_ranges<I>(ranges: I) -> Self
+    where
+        I: IntoIterator<Item = ops::RangeInclusive<T>>,
+    {
+        let ranges = ranges
+            .into_iter()
+            .map(|r| {
+                let (start, end) = r.into_inner();
+                Element::from_bounds(start, end)
+            })
+            .collect();
+
+        RangedSet::from_elements(ranges)
+    }
+
+    /// Adds a non-empty range of values to the set, skipping the search
+    /// for where it goes when it comes after every value already in
+    /// the set
+    fn push_span(&mut self, range: Span<T>) -> bool {
+        let index = match self.ranges.last() {
+            Some(last) if range.start <= *last.end() => return self.insert_span(range),
+            Some(last) if last.adjacent_to(&range.start) => self.ranges.len() - 1,
+            _ => self.ranges.len(),
+        };
+
+        let start = match self.ranges.get(index) {
+            Some(last) => last.start().clone(),
+            None => range.start,
+        };
+
+        self.ranges.truncate(index);
+        self.ranges.push(Element::from_bounds(start, range.end));
+        self.recount_from(index);
+        true
+    }
+
+    fn from_elements(ranges: Vec<Element<T>>) -> Self {
+        let mut set = RangedSet {
+            ranges,
+            counts: Vec::new(),
+            base: 0,
+        };
+        set.recount_from(0);
+
+        set
+    }
+
+    fn recount_from(&mut self, index: usize) {
+        self.counts.truncate(index);
+        if index == 0 {
+            self.base = 0;
+        }
+
+        let mut total = self.counts.last().cloned().unwrap_or(self.base);
+        for element in &self.ranges[index..] {
+            total = total.saturating_add(element.len());
+            self.counts.push(total);
+        }
+    }
+
+    fn count_before(&self, index: usize) -> u128 {
+        match index.checked_sub(1) {
+            Some(before) => self.counts[before] - self.base,
+            None => 0,
+        }
+    }
+
+    fn find_index_for(&self, value: &T) -> Result<usize, usize> {
+        self.ranges
+            .binary_search_by(|member| member.cmp_value(value))
+    }
+
+    /// Returns the indices of the elements with at least one value in
+    /// a range
+    fn overlapping(&self, range: &Span<T>) -> ops::Range<usize> {
+        let first = match self.find_index_for(&range.start) {
+            Ok(index) | Err(index) => index,
+        };
+        let last = match self.find_index_for(&range.end) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        };
+
+        first..last
+    }
+}
+
+impl<T: Discrete + fmt::Debug> fmt::Debug for RangedSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RangedSet")
+            .field("ranges", &self.ranges)
+            .finish()
+    }
+}
+
+// Sets are always kept in canonical form, so two sets hold the same
+// values exactly when they hold the same elements. The counts follow
+// from the elements and don't need comparing.
+impl<T: Discrete> PartialEq for RangedSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ranges == other.ranges
+    }
+}
+
+impl<T: Discrete> Eq for RangedSet<T> {}
+
+impl<T: Discrete + Hash> Hash for RangedSet<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ranges.hash(state);
+    }
+}
+
+impl<T: Discrete> PartialOrd for RangedSet<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares the values of the sets in order, like `BTreeSet` does,
+/// without stepping through the values of each range.
+impl<T: Discrete> Ord for RangedSet<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let mut a = self.ranges.iter();
+        let mut b = other.ranges.iter();
+
+        loop {
+            let (x, y) = match (a.next(), b.next()) {
+                (Some(x), Some(y)) => (x, y),
+                (Some(_), None) => return Ordering::Greater,
+                (None, Some(_)) => return Ordering::Less,
+                (None, None) => return Ordering::Equal,
+            };
+
+            // When two ranges start together but one ends first, the
+            // next value of the shorter one can't follow straight on
+            // from its end, so it's larger than the longer one's next
+            // value. That's unless there isn't a next value at all.
+            let ordering = x
+                .start()
+                .cmp(y.start())
+                .then_with(|| match x.end().cmp(y.end()) {
+                    Ordering::Less if a.as_slice().is_empty() => Ordering::Less,
+                    Ordering::Less => Ordering::Greater,
+                    Ordering::Greater if b.as_slice().is_empty() => Ordering::Greater,
+                    Ordering::Greater => Ordering::Less,
+                    Ordering::Equal => Ordering::Equal,
+                });
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+    }
+}
+
+impl<T: Discrete> IntoIterator for RangedSet<T> {
+    type IntoIter = IntoIter<T>;
+    type Item = T;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter::new(self.ranges)
+    }
+}
+
+impl<'a, T: Discrete> IntoIterator for &'a RangedSet<T> {
+    type IntoIter = Iter<'a, T>;
+    type Item = T;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Discrete> FromIterator<T> for RangedSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = RangedSet::new();
+        set.extend(iter);
+
+        set
+    }
+}
+
+impl<T: Discrete> FromIterator<ops::RangeInclusive<T>> for RangedSet<T> {
+    fn from_iter<I: IntoIterator<Item = ops::RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = RangedSet::new();
+        set.extend(iter);
+
+        set
+    }
+}
+
+impl<T: Discrete> Extend<T> for RangedSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_span(Span::new(value.clone(), value));
+        }
+    }
+}
+
+impl<'a, T: Discrete + Copy + 'a> Extend<&'a T> for RangedSet<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl<T: Discrete> Extend<ops::RangeInclusive<T>> for RangedSet<T> {
+    fn extend<I: IntoIterator<Item = ops::RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            if let Some(range) = Span::from_bounds(&range) {
+                self.push_span(range);
+            }
+        }
+    }
+}
+
+impl<T: Discrete, const N: usize> From<[T; N]> for RangedSet<T> {
+    fn from(values: [T; N]) -> Self {
+        IntoIterator::into_iter(values).collect()
+    }
+}
+
+impl<T: Discrete> From<Vec<T>> for RangedSet<T> {
+    fn from(values: Vec<T>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl<T: Discrete> From<BTreeSet<T>> for RangedSet<T> {
+    fn from(values: BTreeSet<T>) -> Self {
+        values.into_iter().collect()
     }
 }
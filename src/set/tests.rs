@@ -1,6 +1,7 @@
 use crate::element::Element::{Range, Single};
-use crate::range_inclusive::RangeInclusive;
 use crate::set::RangedSet;
+use crate::span::Span;
+use std::ops::Bound;
 
 #[test]
 fn contains_value_on_set_with_no_elements() {
@@ -13,9 +14,7 @@ fn contains_value_on_set_with_no_elements() {
 
 #[test]
 fn contains_value_on_set_with_single_elements() {
-    let rs = RangedSet {
-        ranges: vec![Single(1), Single(3)],
-    };
+    let rs = RangedSet::from_elements(vec![Single(1), Single(3)]);
 
     assert!(!rs.contains(&0));
     assert!(rs.contains(&1));
@@ -26,12 +25,7 @@ fn contains_value_on_set_with_single_elements() {
 
 #[test]
 fn contains_value_on_set_with_range_elements() {
-    let rs = RangedSet {
-        ranges: vec![
-            Range(RangeInclusive::new(0, 1)),
-            Range(RangeInclusive::new(5, 7)),
-        ],
-    };
+    let rs = RangedSet::from_elements(vec![Range(Span::new(0, 1)), Range(Span::new(5, 7))]);
 
     assert!(rs.contains(&0));
     assert!(rs.contains(&1));
@@ -47,9 +41,7 @@ fn contains_value_on_set_with_range_elements() {
 
 #[test]
 fn contains_value_on_set_with_mixed_elements() {
-    let rs = RangedSet {
-        ranges: vec![Range(RangeInclusive::new(0, 1)), Single(4)],
-    };
+    let rs = RangedSet::from_elements(vec![Range(Span::new(0, 1)), Single(4)]);
 
     assert!(rs.contains(&0));
     assert!(rs.contains(&1));
@@ -96,12 +88,7 @@ fn insert_noncontiguous_value_with_single_elements() {
 
 #[test]
 fn insert_noncontiguous_value_with_range_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![
-            Range(RangeInclusive::new(2, 3)),
-            Range(RangeInclusive::new(7, 8)),
-        ],
-    };
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(2, 3)), Range(Span::new(7, 8))]);
 
     assert!(rs.insert(0));
     assert!(rs.insert(5));
@@ -111,9 +98,9 @@ fn insert_noncontiguous_value_with_range_elements() {
         &rs.ranges[..],
         &[
             Single(0),
-            Range(RangeInclusive::new(2, 3)),
+            Range(Span::new(2, 3)),
             Single(5),
-            Range(RangeInclusive::new(7, 8)),
+            Range(Span::new(7, 8)),
             Single(10)
         ]
     );
@@ -121,9 +108,7 @@ fn insert_noncontiguous_value_with_range_elements() {
 
 #[test]
 fn insert_noncontiguous_value_with_mixed_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![Single(0), Range(RangeInclusive::new(4, 5)), Single(9)],
-    };
+    let mut rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(4, 5)), Single(9)]);
 
     assert!(rs.insert(2));
     assert!(rs.insert(7));
@@ -133,7 +118,7 @@ fn insert_noncontiguous_value_with_mixed_elements() {
         &[
             Single(0),
             Single(2),
-            Range(RangeInclusive::new(4, 5)),
+            Range(Span::new(4, 5)),
             Single(7),
             Single(9)
         ]
@@ -142,9 +127,7 @@ fn insert_noncontiguous_value_with_mixed_elements() {
 
 #[test]
 fn insert_contiguous_value_with_single_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![Single(0), Single(4), Single(6), Single(8)],
-    };
+    let mut rs = RangedSet::from_elements(vec![Single(0), Single(4), Single(6), Single(8)]);
 
     assert!(rs.insert(1));
     assert!(rs.insert(3));
@@ -153,23 +136,21 @@ fn insert_contiguous_value_with_single_elements() {
     assert_eq!(
         &rs.ranges[..],
         &[
-            Range(RangeInclusive::new(0, 1)),
-            Range(RangeInclusive::new(3, 4)),
-            Range(RangeInclusive::new(6, 8))
+            Range(Span::new(0, 1)),
+            Range(Span::new(3, 4)),
+            Range(Span::new(6, 8))
         ]
     );
 }
 
 #[test]
 fn insert_contiguous_value_with_range_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![
-            Range(RangeInclusive::new(0, 1)),
-            Range(RangeInclusive::new(5, 6)),
-            Range(RangeInclusive::new(8, 9)),
-            Range(RangeInclusive::new(11, 12)),
-        ],
-    };
+    let mut rs = RangedSet::from_elements(vec![
+        Range(Span::new(0, 1)),
+        Range(Span::new(5, 6)),
+        Range(Span::new(8, 9)),
+        Range(Span::new(11, 12)),
+    ]);
 
     assert!(rs.insert(2));
     assert!(rs.insert(4));
@@ -178,23 +159,21 @@ fn insert_contiguous_value_with_range_elements() {
     assert_eq!(
         &rs.ranges[..],
         &[
-            Range(RangeInclusive::new(0, 2)),
-            Range(RangeInclusive::new(4, 6)),
-            Range(RangeInclusive::new(8, 12))
+            Range(Span::new(0, 2)),
+            Range(Span::new(4, 6)),
+            Range(Span::new(8, 12))
         ]
     );
 }
 
 #[test]
 fn insert_contiguous_value_with_mixed_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![Single(0), Range(RangeInclusive::new(2, 3)), Single(5)],
-    };
+    let mut rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(2, 3)), Single(5)]);
 
     assert!(rs.insert(1));
     assert!(rs.insert(4));
 
-    assert_eq!(&rs.ranges[..], &[Range(RangeInclusive::new(0, 5))]);
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 5))]);
 }
 
 #[test]
@@ -205,7 +184,7 @@ fn insert_all_legal_values() {
         assert!(rs.insert(i as u8));
     }
 
-    assert_eq!(&rs.ranges[..], &[Range(RangeInclusive::new(0, 255))]);
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 255))]);
 }
 
 #[test]
@@ -218,9 +197,7 @@ fn take_on_empty_set() {
 
 #[test]
 fn take_value_on_set_with_single_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![Single(0), Single(4), Single(6), Single(8)],
-    };
+    let mut rs = RangedSet::from_elements(vec![Single(0), Single(4), Single(6), Single(8)]);
 
     assert_eq!(rs.take(&4), Some(4));
     assert_eq!(rs.take(&6), Some(6));
@@ -232,13 +209,11 @@ fn take_value_on_set_with_single_elements() {
 
 #[test]
 fn take_value_on_set_with_range_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![
-            Range(RangeInclusive::new(0, 1)),
-            Range(RangeInclusive::new(5, 6)),
-            Range(RangeInclusive::new(8, 10)),
-        ],
-    };
+    let mut rs = RangedSet::from_elements(vec![
+        Range(Span::new(0, 1)),
+        Range(Span::new(5, 6)),
+        Range(Span::new(8, 10)),
+    ]);
 
     assert_eq!(rs.take(&0), Some(0));
     assert_eq!(rs.take(&2), None);
@@ -254,9 +229,7 @@ fn take_value_on_set_with_range_elements() {
 
 #[test]
 fn take_value_on_set_with_mixed_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![Single(0), Range(RangeInclusive::new(2, 3)), Single(5)],
-    };
+    let mut rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(2, 3)), Single(5)]);
 
     assert_eq!(rs.take(&0), Some(0));
     assert_eq!(rs.take(&2), Some(2));
@@ -276,9 +249,7 @@ fn remove_on_empty_set() {
 
 #[test]
 fn remove_value_on_set_with_single_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![Single(0), Single(4), Single(6), Single(8)],
-    };
+    let mut rs = RangedSet::from_elements(vec![Single(0), Single(4), Single(6), Single(8)]);
 
     assert!(rs.remove(&4));
     assert!(rs.remove(&6));
@@ -290,13 +261,11 @@ fn remove_value_on_set_with_single_elements() {
 
 #[test]
 fn remove_value_on_set_with_range_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![
-            Range(RangeInclusive::new(0, 1)),
-            Range(RangeInclusive::new(5, 6)),
-            Range(RangeInclusive::new(8, 10)),
-        ],
-    };
+    let mut rs = RangedSet::from_elements(vec![
+        Range(Span::new(0, 1)),
+        Range(Span::new(5, 6)),
+        Range(Span::new(8, 10)),
+    ]);
 
     assert!(rs.remove(&0));
     assert!(!rs.remove(&2));
@@ -312,9 +281,7 @@ fn remove_value_on_set_with_range_elements() {
 
 #[test]
 fn remove_value_on_set_with_mixed_elements() {
-    let mut rs = RangedSet {
-        ranges: vec![Single(0), Range(RangeInclusive::new(2, 3)), Single(5)],
-    };
+    let mut rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(2, 3)), Single(5)]);
 
     assert!(rs.remove(&0));
     assert!(rs.remove(&2));
@@ -323,3 +290,975 @@ fn remove_value_on_set_with_mixed_elements() {
 
     assert_eq!(&rs.ranges[..], &[Single(3)]);
 }
+
+#[test]
+fn insert_range_on_empty_set() {
+    let mut rs = RangedSet::new();
+
+    assert!(rs.insert_range(0..=5));
+    assert!(rs.insert_range(10..11));
+
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 5)), Single(10)]);
+}
+
+#[test]
+fn insert_range_that_is_already_contained() {
+    let mut rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(2, 8))]);
+
+    assert!(!rs.insert_range(0..=0));
+    assert!(!rs.insert_range(2..=8));
+    assert!(!rs.insert_range(3..6));
+
+    assert_eq!(&rs.ranges[..], &[Single(0), Range(Span::new(2, 8))]);
+}
+
+#[test]
+fn insert_range_adjacent_to_elements() {
+    let mut rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(6, 8)), Single(12)]);
+
+    assert!(rs.insert_range(1..=2));
+    assert!(rs.insert_range(9..12));
+
+    assert_eq!(
+        &rs.ranges[..],
+        &[Range(Span::new(0, 2)), Range(Span::new(6, 12))]
+    );
+}
+
+#[test]
+fn insert_range_overlapping_elements() {
+    let mut rs = RangedSet::from_elements(vec![
+        Range(Span::new(0, 3)),
+        Single(6),
+        Range(Span::new(9, 12)),
+        Single(20),
+    ]);
+
+    assert!(rs.insert_range(2..=10));
+
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 12)), Single(20)]);
+}
+
+#[test]
+fn insert_range_swallowing_elements() {
+    let mut rs = RangedSet::from_elements(vec![
+        Single(0),
+        Single(4),
+        Range(Span::new(6, 7)),
+        Single(11),
+    ]);
+
+    assert!(rs.insert_range(3..=9));
+
+    assert_eq!(
+        &rs.ranges[..],
+        &[Single(0), Range(Span::new(3, 9)), Single(11)]
+    );
+}
+
+#[test]
+fn insert_empty_range() {
+    let mut rs = RangedSet::from_elements(vec![Single(5)]);
+
+    let (start, end) = (8, 2);
+    assert!(!rs.insert_range(3..3));
+    assert!(!rs.insert_range(start..=end));
+
+    assert_eq!(&rs.ranges[..], &[Single(5)]);
+}
+
+#[test]
+fn insert_range_at_type_limits() {
+    let mut rs: RangedSet<u8> = RangedSet::new();
+
+    assert!(rs.insert_range(250..=255));
+    assert!(rs.insert_range(0..=5));
+    assert!(rs.insert_range(6..250));
+    assert!(!rs.insert(128));
+
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 255))]);
+}
+
+#[test]
+fn insert_unbounded_ranges() {
+    let mut rs: RangedSet<u8> = RangedSet::new();
+
+    assert!(rs.insert_range(250..));
+    assert!(rs.insert_range(..=5));
+    assert!(rs.insert_range((Bound::Excluded(10), Bound::Included(20))));
+    assert_eq!(
+        &rs.ranges[..],
+        &[
+            Range(Span::new(0, 5)),
+            Range(Span::new(11, 20)),
+            Range(Span::new(250, 255))
+        ]
+    );
+
+    assert!(rs.insert_range(..));
+    assert!(!rs.insert_range(..));
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 255))]);
+}
+
+#[test]
+fn insert_range_excluding_type_limits() {
+    let mut rs: RangedSet<u8> = RangedSet::new();
+
+    assert!(!rs.insert_range(..0));
+    assert!(!rs.insert_range((Bound::Excluded(255), Bound::Unbounded)));
+    assert!(rs.ranges.is_empty());
+}
+
+#[test]
+fn remove_range_on_empty_set() {
+    let mut rs = RangedSet::new();
+
+    assert_eq!(rs.remove_range(0..=10), 0);
+    assert!(rs.ranges.is_empty());
+}
+
+#[test]
+fn remove_range_between_elements() {
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(0, 2)), Single(8)]);
+
+    assert_eq!(rs.remove_range(3..=7), 0);
+
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 2)), Single(8)]);
+}
+
+#[test]
+fn remove_range_in_middle_of_element() {
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(0, 20))]);
+
+    assert_eq!(rs.remove_range(5..=9), 5);
+    assert_eq!(rs.remove_range(11..20), 9);
+
+    assert_eq!(
+        &rs.ranges[..],
+        &[Range(Span::new(0, 4)), Single(10), Single(20)]
+    );
+}
+
+#[test]
+fn remove_range_trimming_and_dropping_elements() {
+    let mut rs = RangedSet::from_elements(vec![
+        Range(Span::new(0, 4)),
+        Single(6),
+        Range(Span::new(8, 9)),
+        Range(Span::new(11, 15)),
+    ]);
+
+    assert_eq!(rs.remove_range(3..=12), 7);
+
+    assert_eq!(
+        &rs.ranges[..],
+        &[Range(Span::new(0, 2)), Range(Span::new(13, 15))]
+    );
+}
+
+#[test]
+fn remove_empty_range() {
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(0, 9))]);
+
+    let (start, end) = (6, 3);
+    assert_eq!(rs.remove_range(3..3), 0);
+    assert_eq!(rs.remove_range(start..=end), 0);
+
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 9))]);
+}
+
+#[test]
+fn remove_range_at_type_limits() {
+    let mut rs: RangedSet<u8> = RangedSet::new();
+    rs.insert_range(0..=255);
+
+    assert_eq!(rs.remove_range(1..255), 254);
+    assert_eq!(&rs.ranges[..], &[Single(0), Single(255)]);
+
+    assert_eq!(rs.remove_range(0..=255), 2);
+    assert!(rs.ranges.is_empty());
+}
+
+#[test]
+fn remove_range_counting_whole_domain() {
+    let mut rs: RangedSet<u64> = RangedSet::new();
+    rs.insert_range(0..=u64::MAX);
+
+    assert_eq!(rs.remove_range(0..=u64::MAX), 1 << 64);
+}
+
+#[test]
+fn remove_unbounded_ranges() {
+    let mut rs: RangedSet<i8> = RangedSet::new();
+    rs.insert_range(..);
+
+    assert_eq!(rs.remove_range(..-100), 28);
+    assert_eq!(rs.remove_range(100..), 28);
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(-100, 99))]);
+
+    assert_eq!(rs.remove_range(..), 200);
+    assert!(rs.ranges.is_empty());
+}
+
+#[test]
+fn range_queries_with_unbounded_ranges() {
+    let mut rs: RangedSet<u8> = RangedSet::new();
+    rs.insert_range(0..=9);
+    rs.insert_range(200..);
+
+    assert!(rs.contains_range(..=9));
+    assert!(rs.contains_range(200..));
+    assert!(!rs.contains_range(..));
+    assert!(rs.intersects_range(..));
+    assert!(!rs.intersects_range(10..200));
+
+    let gaps: Vec<_> = rs.gaps_within(..).collect();
+    assert_eq!(gaps, vec![10..=199]);
+
+    let complement: Vec<_> = rs.complement_within(5..).ranges().collect();
+    assert_eq!(complement, vec![10..=199]);
+}
+
+#[test]
+fn set_algebra_iterators() {
+    let a = RangedSet::from_elements(vec![Range(Span::new(0, 5)), Single(9)]);
+    let b = RangedSet::from_elements(vec![Range(Span::new(3, 8)), Single(12)]);
+
+    assert_eq!(a.union(&b).collect::<Vec<_>>(), vec![0..=9, 12..=12]);
+    assert_eq!(a.intersection(&b).collect::<Vec<_>>(), vec![3..=5]);
+    assert_eq!(a.difference(&b).collect::<Vec<_>>(), vec![0..=2, 9..=9]);
+    assert_eq!(b.difference(&a).collect::<Vec<_>>(), vec![6..=8, 12..=12]);
+    assert_eq!(
+        a.symmetric_difference(&b).collect::<Vec<_>>(),
+        vec![0..=2, 6..=9, 12..=12]
+    );
+}
+
+#[test]
+fn set_algebra_in_place() {
+    let b = RangedSet::from_elements(vec![Range(Span::new(3, 8)), Single(12)]);
+
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(0, 5)), Single(9)]);
+    rs.union_with(&b);
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 9)), Single(12)]);
+
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(0, 5)), Single(9)]);
+    rs.intersection_with(&b);
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(3, 5))]);
+
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(0, 5)), Single(9)]);
+    rs.difference_with(&b);
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 2)), Single(9)]);
+
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(0, 5)), Single(9)]);
+    rs.symmetric_difference_with(&b);
+    assert_eq!(
+        &rs.ranges[..],
+        &[Range(Span::new(0, 2)), Range(Span::new(6, 9)), Single(12)]
+    );
+}
+
+#[test]
+fn set_algebra_operators() {
+    let a = RangedSet::from_elements(vec![Range(Span::new(0, 5)), Single(9)]);
+    let b = RangedSet::from_elements(vec![Range(Span::new(3, 8)), Single(12)]);
+
+    assert_eq!(&(&a | &b).ranges[..], &[Range(Span::new(0, 9)), Single(12)]);
+    assert_eq!(&(&a & &b).ranges[..], &[Range(Span::new(3, 5))]);
+    assert_eq!(&(&a - &b).ranges[..], &[Range(Span::new(0, 2)), Single(9)]);
+    assert_eq!(
+        &(&a ^ &b).ranges[..],
+        &[Range(Span::new(0, 2)), Range(Span::new(6, 9)), Single(12)]
+    );
+
+    let mut rs = a - &b;
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 2)), Single(9)]);
+    rs |= b;
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 9)), Single(12)]);
+}
+
+#[test]
+fn set_algebra_operators_mixing_owned_and_borrowed() {
+    let a = RangedSet::from_elements(vec![Range(Span::new(0, 5)), Single(9)]);
+    let b = RangedSet::from_elements(vec![Range(Span::new(3, 8)), Single(12)]);
+
+    assert_eq!(&a - b.clone(), &a - &b);
+    assert_eq!(a.clone() - &b, &a - &b);
+    assert_eq!(&a ^ b.clone(), a.clone() ^ b.clone());
+    assert_eq!(&b & a.clone(), &a & &b);
+    assert_eq!(&a | b.clone(), a | b);
+}
+
+#[test]
+fn contains_range_on_set_with_mixed_elements() {
+    let rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(3, 8))]);
+
+    assert!(rs.contains_range(0..=0));
+    assert!(rs.contains_range(3..=8));
+    assert!(rs.contains_range(4..7));
+    assert!(!rs.contains_range(0..=1));
+    assert!(!rs.contains_range(2..=4));
+    assert!(!rs.contains_range(8..=9));
+    assert!(rs.contains_range(20..20));
+}
+
+#[test]
+fn intersects_range_on_set_with_mixed_elements() {
+    let rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(3, 8))]);
+
+    assert!(rs.intersects_range(0..=2));
+    assert!(rs.intersects_range(1..=3));
+    assert!(rs.intersects_range(8..20));
+    assert!(!rs.intersects_range(1..3));
+    assert!(!rs.intersects_range(9..=20));
+    assert!(!rs.intersects_range(5..5));
+}
+
+#[test]
+fn subset_and_superset() {
+    let empty = RangedSet::new();
+    let a = RangedSet::from_elements(vec![Range(Span::new(0, 10)), Range(Span::new(20, 30))]);
+    let b = RangedSet::from_elements(vec![
+        Single(0),
+        Range(Span::new(4, 6)),
+        Range(Span::new(20, 30)),
+    ]);
+    let c = RangedSet::from_elements(vec![Range(Span::new(8, 12))]);
+
+    assert!(empty.is_subset(&a));
+    assert!(a.is_subset(&a));
+    assert!(b.is_subset(&a));
+    assert!(!a.is_subset(&b));
+    assert!(!c.is_subset(&a));
+    assert!(!a.is_subset(&empty));
+
+    assert!(a.is_superset(&empty));
+    assert!(a.is_superset(&b));
+    assert!(!b.is_superset(&a));
+    assert!(!a.is_superset(&c));
+}
+
+#[test]
+fn disjoint_sets() {
+    let empty = RangedSet::new();
+    let a = RangedSet::from_elements(vec![Range(Span::new(0, 4)), Single(10)]);
+    let b = RangedSet::from_elements(vec![Range(Span::new(5, 9)), Single(11)]);
+    let c = RangedSet::from_elements(vec![Range(Span::new(7, 10))]);
+
+    assert!(a.is_disjoint(&empty));
+    assert!(empty.is_disjoint(&a));
+    assert!(a.is_disjoint(&b));
+    assert!(b.is_disjoint(&a));
+    assert!(!a.is_disjoint(&c));
+    assert!(!c.is_disjoint(&b));
+    assert!(!a.is_disjoint(&a));
+}
+
+#[test]
+fn iterate_over_set() {
+    let rs = RangedSet::from_elements(vec![Range(Span::new(0, 2)), Single(5)]);
+
+    assert_eq!(rs.iter().collect::<Vec<_>>(), vec![0, 1, 2, 5]);
+    assert_eq!(rs.ranges().collect::<Vec<_>>(), vec![0..=2, 5..=5]);
+    assert_eq!(
+        (&rs).into_iter().rev().collect::<Vec<_>>(),
+        vec![5, 2, 1, 0]
+    );
+
+    let mut values = Vec::new();
+    for value in &rs {
+        values.push(value);
+    }
+    assert_eq!(values, vec![0, 1, 2, 5]);
+
+    assert_eq!(rs.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 5]);
+}
+
+#[test]
+fn range_of_values_in_set() {
+    let rs = RangedSet::from_elements(vec![
+        Range(Span::new(0, 4)),
+        Single(6),
+        Range(Span::new(8, 9)),
+    ]);
+
+    let values: Vec<_> = rs.range(3..=8).collect();
+    assert_eq!(values, vec![3, 4, 6, 8]);
+
+    let values: Vec<_> = rs.range(3..=8).rev().collect();
+    assert_eq!(values, vec![8, 6, 4, 3]);
+
+    let values: Vec<_> = rs.range(1..3).collect();
+    assert_eq!(values, vec![1, 2]);
+
+    let values: Vec<_> = rs.range(..).collect();
+    assert_eq!(values, vec![0, 1, 2, 3, 4, 6, 8, 9]);
+
+    let (start, end) = (8, 2);
+    assert_eq!(rs.range(start..end).count(), 0);
+    assert_eq!(rs.range(5..6).count(), 0);
+    assert_eq!(rs.range(10..).count(), 0);
+}
+
+#[test]
+fn range_of_values_from_both_ends() {
+    let rs = RangedSet::from_elements(vec![Range(Span::new(0, 4)), Range(Span::new(8, 12))]);
+
+    let mut range = rs.range(2..=10);
+    assert_eq!(range.size_hint(), (6, Some(6)));
+    assert_eq!(range.next(), Some(2));
+    assert_eq!(range.next_back(), Some(10));
+    assert_eq!(range.next_back(), Some(9));
+    assert_eq!(range.next_back(), Some(8));
+    assert_eq!(range.next_back(), Some(4));
+    assert_eq!(range.next(), Some(3));
+    assert_eq!(range.next(), None);
+    assert_eq!(range.next_back(), None);
+}
+
+#[test]
+fn range_of_values_at_type_limits() {
+    let mut rs: RangedSet<u8> = RangedSet::new();
+    rs.insert_range(..);
+
+    assert_eq!(rs.range(250..).len(), 6);
+    assert_eq!(rs.range(..=5).next_back(), Some(5));
+    assert_eq!(rs.range(..).len(), 256);
+}
+
+#[test]
+fn ranges_in_set_clipped_to_range() {
+    let rs = RangedSet::from_elements(vec![
+        Range(Span::new(0, 4)),
+        Single(6),
+        Range(Span::new(8, 12)),
+    ]);
+
+    let ranges: Vec<_> = rs.ranges_in(2..=10).collect();
+    assert_eq!(ranges, vec![2..=4, 6..=6, 8..=10]);
+
+    let ranges: Vec<_> = rs.ranges_in(2..=10).rev().collect();
+    assert_eq!(ranges, vec![8..=10, 6..=6, 2..=4]);
+
+    let ranges: Vec<_> = rs.ranges_in(9..=10).collect();
+    assert_eq!(ranges, vec![9..=10]);
+
+    assert_eq!(rs.ranges_in(..).len(), 3);
+    assert_eq!(rs.ranges_in(5..=5).count(), 0);
+}
+
+#[test]
+fn gaps_in_set() {
+    let rs = RangedSet::from_elements(vec![
+        Single(0),
+        Range(Span::new(3, 5)),
+        Range(Span::new(8, 10)),
+        Single(15),
+    ]);
+
+    assert_eq!(rs.gaps().collect::<Vec<_>>(), vec![1..=2, 6..=7, 11..=14]);
+    assert_eq!(
+        rs.gaps_within(-5..=20).collect::<Vec<_>>(),
+        vec![-5..=-1, 1..=2, 6..=7, 11..=14, 16..=20]
+    );
+    assert_eq!(
+        rs.gaps_within(4..12).collect::<Vec<_>>(),
+        vec![6..=7, 11..=11]
+    );
+    assert_eq!(rs.gaps_within(6..=7).collect::<Vec<_>>(), vec![6..=7]);
+    assert_eq!(rs.gaps_within(3..=5).next(), None);
+    assert_eq!(rs.gaps_within(7..7).next(), None);
+}
+
+#[test]
+fn complement_of_set() {
+    let empty: RangedSet<u8> = RangedSet::new();
+    let full = RangedSet::from_elements(vec![Range(Span::new(0u8, 255))]);
+    let rs = RangedSet::from_elements(vec![Single(0u8), Range(Span::new(10, 20)), Single(255)]);
+
+    assert_eq!(&empty.complement().ranges[..], &[Range(Span::new(0, 255))]);
+    assert!(full.complement().ranges.is_empty());
+    assert_eq!(
+        &rs.complement().ranges[..],
+        &[Range(Span::new(1, 9)), Range(Span::new(21, 254))]
+    );
+    assert_eq!(&(!&rs).ranges[..], &rs.complement().ranges[..]);
+    assert_eq!(
+        &(!!rs).ranges[..],
+        &[Single(0), Range(Span::new(10, 20)), Single(255)]
+    );
+}
+
+#[test]
+fn complement_of_set_within_range() {
+    let rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(10, 20))]);
+
+    assert_eq!(
+        &rs.complement_within(-2..=25).ranges[..],
+        &[
+            Range(Span::new(-2, -1)),
+            Range(Span::new(1, 9)),
+            Range(Span::new(21, 25))
+        ]
+    );
+    assert_eq!(
+        &rs.complement_within(5..=11).ranges[..],
+        &[Range(Span::new(5, 9))]
+    );
+    assert!(rs.complement_within(12..15).ranges.is_empty());
+}
+
+#[test]
+fn cardinality_of_empty_set() {
+    let rs: RangedSet<i32> = RangedSet::new();
+
+    assert!(rs.is_empty());
+    assert_eq!(rs.len(), 0);
+    assert_eq!(rs.range_count(), 0);
+}
+
+#[test]
+fn cardinality_of_set_with_mixed_elements() {
+    let mut rs = RangedSet::from_elements(vec![Single(-3), Range(Span::new(0, 9)), Single(12)]);
+
+    assert!(!rs.is_empty());
+    assert_eq!(rs.len(), 12);
+    assert_eq!(rs.range_count(), 3);
+
+    rs.take(&5);
+    assert_eq!(rs.len(), 11);
+    assert_eq!(rs.range_count(), 4);
+
+    rs.remove_range(-3..=12);
+    assert!(rs.is_empty());
+    assert_eq!(rs.len(), 0);
+}
+
+#[test]
+fn cardinality_of_whole_domain() {
+    let mut rs: RangedSet<i64> = RangedSet::new();
+    rs.insert_range(i64::MIN..=i64::MAX);
+
+    assert_eq!(rs.len(), 1 << 64);
+    assert_eq!(rs.range_count(), 1);
+}
+
+#[test]
+fn first_and_last_on_empty_set() {
+    let mut rs: RangedSet<i32> = RangedSet::new();
+
+    assert_eq!(rs.first(), None);
+    assert_eq!(rs.last(), None);
+    assert_eq!(rs.pop_first(), None);
+    assert_eq!(rs.pop_last(), None);
+}
+
+#[test]
+fn first_and_last_on_set_with_mixed_elements() {
+    let rs = RangedSet::from_elements(vec![Single(-4), Range(Span::new(2, 6))]);
+
+    assert_eq!(rs.first(), Some(&-4));
+    assert_eq!(rs.last(), Some(&6));
+}
+
+#[test]
+fn pop_first_on_set_with_mixed_elements() {
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(0, 2)), Single(5)]);
+
+    assert_eq!(rs.pop_first(), Some(0));
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(1, 2)), Single(5)]);
+    assert_eq!(rs.pop_first(), Some(1));
+    assert_eq!(&rs.ranges[..], &[Single(2), Single(5)]);
+    assert_eq!(rs.pop_first(), Some(2));
+    assert_eq!(&rs.ranges[..], &[Single(5)]);
+    assert_eq!(rs.pop_first(), Some(5));
+    assert!(rs.ranges.is_empty());
+}
+
+#[test]
+fn pop_last_on_set_with_mixed_elements() {
+    let mut rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(3, 5))]);
+
+    assert_eq!(rs.pop_last(), Some(5));
+    assert_eq!(&rs.ranges[..], &[Single(0), Range(Span::new(3, 4))]);
+    assert_eq!(rs.pop_last(), Some(4));
+    assert_eq!(&rs.ranges[..], &[Single(0), Single(3)]);
+    assert_eq!(rs.pop_last(), Some(3));
+    assert_eq!(&rs.ranges[..], &[Single(0)]);
+    assert_eq!(rs.pop_last(), Some(0));
+    assert!(rs.ranges.is_empty());
+}
+
+#[test]
+fn pop_at_type_limits() {
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(0u8, 255))]);
+
+    assert_eq!(rs.pop_first(), Some(0));
+    assert_eq!(rs.pop_last(), Some(255));
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(1, 254))]);
+}
+
+#[test]
+fn neighbours_on_empty_set() {
+    let rs: RangedSet<i32> = RangedSet::new();
+
+    assert_eq!(rs.ceiling(&0), None);
+    assert_eq!(rs.floor(&0), None);
+    assert_eq!(rs.successor(&0), None);
+    assert_eq!(rs.predecessor(&0), None);
+    assert_eq!(rs.next_missing(&0), Some(0));
+    assert_eq!(rs.prev_missing(&0), Some(0));
+}
+
+#[test]
+fn ceiling_and_floor_on_set_with_mixed_elements() {
+    let rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(4, 6)), Single(10)]);
+
+    assert_eq!(rs.ceiling(&-1), Some(0));
+    assert_eq!(rs.ceiling(&1), Some(4));
+    assert_eq!(rs.ceiling(&5), Some(5));
+    assert_eq!(rs.ceiling(&7), Some(10));
+    assert_eq!(rs.ceiling(&11), None);
+
+    assert_eq!(rs.floor(&-1), None);
+    assert_eq!(rs.floor(&3), Some(0));
+    assert_eq!(rs.floor(&5), Some(5));
+    assert_eq!(rs.floor(&9), Some(6));
+    assert_eq!(rs.floor(&11), Some(10));
+}
+
+#[test]
+fn successor_and_predecessor_on_set_with_mixed_elements() {
+    let rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(4, 6)), Single(10)]);
+
+    assert_eq!(rs.successor(&0), Some(4));
+    assert_eq!(rs.successor(&4), Some(5));
+    assert_eq!(rs.successor(&6), Some(10));
+    assert_eq!(rs.successor(&10), None);
+
+    assert_eq!(rs.predecessor(&0), None);
+    assert_eq!(rs.predecessor(&4), Some(0));
+    assert_eq!(rs.predecessor(&6), Some(5));
+    assert_eq!(rs.predecessor(&10), Some(6));
+}
+
+#[test]
+fn missing_values_on_set_with_mixed_elements() {
+    let rs = RangedSet::from_elements(vec![Single(0), Range(Span::new(4, 6)), Single(10)]);
+
+    assert_eq!(rs.next_missing(&0), Some(1));
+    assert_eq!(rs.next_missing(&2), Some(2));
+    assert_eq!(rs.next_missing(&4), Some(7));
+    assert_eq!(rs.next_missing(&10), Some(11));
+
+    assert_eq!(rs.prev_missing(&0), Some(-1));
+    assert_eq!(rs.prev_missing(&6), Some(3));
+    assert_eq!(rs.prev_missing(&8), Some(8));
+    assert_eq!(rs.prev_missing(&10), Some(9));
+}
+
+#[test]
+fn neighbours_at_type_limits() {
+    let rs = RangedSet::from_elements(vec![Range(Span::new(0u8, 3)), Range(Span::new(250, 255))]);
+
+    assert_eq!(rs.successor(&255), None);
+    assert_eq!(rs.predecessor(&0), None);
+    assert_eq!(rs.next_missing(&252), None);
+    assert_eq!(rs.prev_missing(&2), None);
+    assert_eq!(rs.next_missing(&0), Some(4));
+    assert_eq!(rs.prev_missing(&255), Some(249));
+}
+
+fn assert_counts_consistent(rs: &RangedSet<i32>) {
+    let mut total = 0;
+    let expected: Vec<u128> = rs
+        .ranges
+        .iter()
+        .map(|e| {
+            total += e.len();
+            total
+        })
+        .collect();
+
+    let counts: Vec<u128> = rs.counts.iter().map(|total| total - rs.base).collect();
+    assert_eq!(counts, expected);
+}
+
+#[test]
+fn counts_stay_consistent_through_changes() {
+    let mut rs = RangedSet::new();
+    assert_counts_consistent(&rs);
+
+    for value in &[5, 1, 3, 2, 9, 4, 20] {
+        rs.insert(*value);
+        assert_counts_consistent(&rs);
+    }
+
+    rs.insert_range(12..=15);
+    assert_counts_consistent(&rs);
+    rs.take(&3);
+    assert_counts_consistent(&rs);
+    rs.take(&9);
+    assert_counts_consistent(&rs);
+    rs.remove_range(13..=14);
+    assert_counts_consistent(&rs);
+    rs.pop_first();
+    assert_counts_consistent(&rs);
+    rs.pop_last();
+    assert_counts_consistent(&rs);
+
+    let other = RangedSet::from_elements(vec![Range(Span::new(0, 3)), Single(30)]);
+    rs.union_with(&other);
+    assert_counts_consistent(&rs);
+    rs.difference_with(&other);
+    assert_counts_consistent(&rs);
+}
+
+#[test]
+fn rank_on_set_with_mixed_elements() {
+    let rs = RangedSet::from_elements(vec![Single(-5), Range(Span::new(0, 9)), Single(20)]);
+
+    assert_eq!(rs.rank(&-10), 0);
+    assert_eq!(rs.rank(&-5), 0);
+    assert_eq!(rs.rank(&-4), 1);
+    assert_eq!(rs.rank(&0), 1);
+    assert_eq!(rs.rank(&5), 6);
+    assert_eq!(rs.rank(&15), 11);
+    assert_eq!(rs.rank(&20), 11);
+    assert_eq!(rs.rank(&21), 12);
+}
+
+#[test]
+fn select_on_set_with_mixed_elements() {
+    let rs = RangedSet::from_elements(vec![Single(-5), Range(Span::new(0, 9)), Single(20)]);
+
+    assert_eq!(rs.select(0), Some(-5));
+    assert_eq!(rs.select(1), Some(0));
+    assert_eq!(rs.select(10), Some(9));
+    assert_eq!(rs.select(11), Some(20));
+    assert_eq!(rs.select(12), None);
+
+    for position in 0..12 {
+        let value = rs.select(position).unwrap();
+        assert_eq!(rs.rank(&value), position);
+    }
+}
+
+#[test]
+fn rank_and_select_at_type_limits() {
+    let mut rs: RangedSet<u64> = RangedSet::new();
+    rs.insert_range(0..=u64::MAX);
+
+    assert_eq!(rs.rank(&u64::MAX), (1 << 64) - 1);
+    assert_eq!(rs.select((1 << 64) - 1), Some(u64::MAX));
+    assert_eq!(rs.select(1 << 64), None);
+}
+
+#[test]
+fn pop_first_moves_base_instead_of_counts() {
+    let mut rs = RangedSet::from_elements(vec![
+        Range(Span::new(0, 4)),
+        Single(8),
+        Range(Span::new(10, 12)),
+    ]);
+    let counts = rs.counts.clone();
+
+    assert_eq!(rs.pop_first(), Some(0));
+    assert_eq!(rs.pop_first(), Some(1));
+    assert_eq!(rs.counts, counts);
+    assert_counts_consistent(&rs);
+    assert_eq!(rs.rank(&10), 4);
+    assert_eq!(rs.select(3), Some(8));
+
+    rs.pop_first();
+    rs.pop_first();
+    rs.pop_first();
+    assert_eq!(rs.len(), 4);
+    assert_eq!(rs.select(0), Some(8));
+    assert_eq!(rs.select(4), None);
+
+    rs.insert(-1);
+    assert_counts_consistent(&rs);
+    assert_eq!(rs.base, 0);
+    assert_eq!(rs.rank(&11), 3);
+}
+
+#[test]
+fn pop_first_from_saturated_counts() {
+    let mut rs: RangedSet<u128> = RangedSet::new();
+    rs.insert_range(..);
+
+    assert_eq!(rs.pop_first(), Some(0));
+    assert_eq!(rs.len(), u128::MAX);
+    assert_eq!(rs.pop_first(), Some(1));
+    assert_eq!(rs.len(), u128::MAX - 1);
+    assert_eq!(rs.select(0), Some(2));
+}
+
+#[test]
+fn collect_sorted_values() {
+    let rs: RangedSet<i32> = vec![0, 1, 2, 4, 6, 7].into_iter().collect();
+
+    assert_eq!(
+        &rs.ranges[..],
+        &[Range(Span::new(0, 2)), Single(4), Range(Span::new(6, 7))]
+    );
+    assert_counts_consistent(&rs);
+}
+
+#[test]
+fn collect_unsorted_and_duplicate_values() {
+    let rs: RangedSet<i32> = vec![6, 2, 0, 7, 1, 4, 2, 0].into_iter().collect();
+
+    assert_eq!(
+        &rs.ranges[..],
+        &[Range(Span::new(0, 2)), Single(4), Range(Span::new(6, 7))]
+    );
+    assert_counts_consistent(&rs);
+}
+
+#[test]
+fn collect_ranges() {
+    let (start, end) = (9, 8);
+    let rs: RangedSet<i32> = vec![0..=2, 3..=4, 10..=12, start..=end, 1..=5, 11..=11]
+        .into_iter()
+        .collect();
+
+    assert_eq!(
+        &rs.ranges[..],
+        &[Range(Span::new(0, 5)), Range(Span::new(10, 12))]
+    );
+    assert_counts_consistent(&rs);
+}
+
+#[test]
+fn extend_set() {
+    let mut rs = RangedSet::from_elements(vec![Range(Span::new(0, 2))]);
+
+    rs.extend(vec![3, 5]);
+    rs.extend(&[7, 1]);
+    rs.extend(vec![6..=6, 10..=12, -5..=-4]);
+
+    assert_eq!(
+        &rs.ranges[..],
+        &[
+            Range(Span::new(-5, -4)),
+            Range(Span::new(0, 3)),
+            Range(Span::new(5, 7)),
+            Range(Span::new(10, 12))
+        ]
+    );
+    assert_counts_consistent(&rs);
+}
+
+#[test]
+fn collect_and_extend_skip_exhausted_ranges() {
+    let mut exhausted = 1..=1;
+    exhausted.next();
+
+    let mut rs = RangedSet::new();
+    assert!(!rs.insert_range(exhausted.clone()));
+
+    rs.extend(vec![exhausted.clone()]);
+    assert!(rs.is_empty());
+
+    let rs: RangedSet<i32> = vec![exhausted, 5..=6].into_iter().collect();
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(5, 6))]);
+}
+
+#[test]
+fn extend_at_type_limits() {
+    let mut rs: RangedSet<u8> = RangedSet::new();
+    rs.extend(vec![0..=127, 128..=254]);
+    rs.extend(vec![255]);
+
+    assert_eq!(&rs.ranges[..], &[Range(Span::new(0, 255))]);
+    assert_eq!(rs.len(), 256);
+}
+
+#[test]
+fn convert_from_collections() {
+    let expected = [Range(Span::new(1, 3)), Single(5)];
+
+    assert_eq!(&RangedSet::from([3, 1, 2, 5]).ranges[..], &expected);
+    assert_eq!(&RangedSet::from(vec![3, 1, 2, 5]).ranges[..], &expected);
+
+    let values: std::collections::BTreeSet<_> = vec![3, 1, 2, 5].into_iter().collect();
+    assert_eq!(&RangedSet::from(values).ranges[..], &expected);
+
+    let empty: [i32; 0] = [];
+    assert!(RangedSet::from(empty).is_empty());
+}
+
+fn hash_of(rs: &RangedSet<i32>) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    rs.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn sets_built_differently_are_identical() {
+    let mut a = RangedSet::new();
+    a.insert_range(0..=9);
+    a.remove_range(3..=4);
+    a.insert(20);
+
+    let mut b = RangedSet::new();
+    for value in vec![20, 9, 0, 2, 5, 1, 8, 6, 7] {
+        b.insert(value);
+    }
+
+    let c: RangedSet<i32> = vec![5..=9, 0..=1, 20..=20, 1..=2].into_iter().collect();
+
+    for other in &[&b, &c] {
+        assert_eq!(&a, *other);
+        assert_eq!(a.ranges, other.ranges);
+        assert_eq!(a.counts, other.counts);
+        assert_eq!(hash_of(&a), hash_of(other));
+    }
+
+    b.insert(3);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn cloned_set_is_independent() {
+    let mut a: RangedSet<i32> = vec![0..=9].into_iter().collect();
+    let b = a.clone();
+    a.remove(&5);
+
+    assert_eq!(b.len(), 10);
+    assert_eq!(a.len(), 9);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn ordering_matches_btree_set() {
+    use std::collections::BTreeSet;
+
+    // Every subset of 0..7, which covers every way runs of values can
+    // line up against each other.
+    let subsets: Vec<Vec<i32>> = (0..128u32)
+        .map(|bits| (0..7).filter(|v| bits & (1 << v) != 0).collect())
+        .collect();
+
+    for a in &subsets {
+        for b in &subsets {
+            let ranged = (RangedSet::from(a.clone()), RangedSet::from(b.clone()));
+            let btree: (BTreeSet<i32>, BTreeSet<i32>) =
+                (a.iter().cloned().collect(), b.iter().cloned().collect());
+
+            assert_eq!(
+                ranged.0.cmp(&ranged.1),
+                btree.0.cmp(&btree.1),
+                "{:?} {:?}",
+                a,
+                b
+            );
+            assert_eq!(ranged.0 == ranged.1, btree.0 == btree.1);
+        }
+    }
+}
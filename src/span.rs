@@ -0,0 +1,312 @@
+#[cfg(test)]
+mod tests;
+
+use crate::discrete::Discrete;
+use std::convert::TryFrom;
+use std::ops::{self, Bound, RangeBounds};
+
+/// A contiguous range of values, including both ends
+///
+/// `Span` is the unit a [`RangedSet`] is built from. Unlike the
+/// standard library's `RangeInclusive` it isn't an iterator, so it can
+/// be copied, compared and hashed freely, and it has the operations
+/// needed to combine spans with each other. A span whose start is
+/// after its end is empty.
+///
+/// Spans convert to and from `RangeInclusive` and `Range`, and can be
+/// passed anywhere a range is accepted.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::{RangedSet, Span};
+///
+/// let a = Span::from(0..=9);
+/// let b = Span::from(5..15);
+///
+/// assert_eq!(a.intersection(&b), Some(Span::new(5, 9)));
+/// assert_eq!(a.union_if_touching(&b), Some(Span::new(0, 14)));
+///
+/// let mut set = RangedSet::new();
+/// set.insert_range(a);
+/// assert_eq!(set.contains_range(Span::new(2, 4)), true);
+/// ```
+///
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Span<T> {
+    pub(crate) start: T,
+    pub(crate) end: T,
+}
+
+impl<T> Span<T> {
+    /// Returns a new span from `start` to `end`, including both
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::Span;
+    ///
+    /// let span = Span::new(1, 5);
+    /// assert_eq!(span.start(), &1);
+    /// assert_eq!(span.end(), &5);
+    /// ```
+    pub fn new(start: T, end: T) -> Self {
+        Span { start, end }
+    }
+
+    /// Returns the first value of the span
+    pub fn start(&self) -> &T {
+        &self.start
+    }
+
+    /// Returns the last value of the span
+    pub fn end(&self) -> &T {
+        &self.end
+    }
+
+    /// Returns the start and end of the span
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::Span;
+    ///
+    /// assert_eq!(Span::new(1, 5).into_inner(), (1, 5));
+    /// ```
+    pub fn into_inner(self) -> (T, T) {
+        (self.start, self.end)
+    }
+}
+
+impl<T: Ord> Span<T> {
+    /// Returns `true` if the span contains no values
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::Span;
+    ///
+    /// assert_eq!(Span::new(1, 1).is_empty(), false);
+    /// assert_eq!(Span::new(2, 1).is_empty(), true);
+    /// assert_eq!(Span::from(1..1).is_empty(), true);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    /// Returns `true` if the span contains a value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::Span;
+    ///
+    /// let span = Span::new(1, 5);
+    /// assert_eq!(span.contains(&0), false);
+    /// assert_eq!(span.contains(&1), true);
+    /// assert_eq!(span.contains(&5), true);
+    /// assert_eq!(span.contains(&6), false);
+    /// ```
+    pub fn contains(&self, value: &T) -> bool {
+        self.start <= *value && *value <= self.end
+    }
+
+    /// Returns `true` if the spans have at least one value in common
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::Span;
+    ///
+    /// let span = Span::new(1, 5);
+    /// assert_eq!(span.overlaps(&Span::new(5, 9)), true);
+    /// assert_eq!(span.overlaps(&Span::new(6, 9)), false);
+    /// ```
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.is_empty() && !other.is_empty() && self.start <= other.end && other.start <= self.end
+    }
+}
+
+impl<T: Clone + Ord> Span<T> {
+    /// Returns the values the spans have in common, or `None` if they
+    /// don't overlap
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::Span;
+    ///
+    /// let span = Span::new(1, 5);
+    /// assert_eq!(span.intersection(&Span::new(3, 9)), Some(Span::new(3, 5)));
+    /// assert_eq!(span.intersection(&Span::new(6, 9)), None);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        if !self.overlaps(other) {
+            return None;
+        }
+
+        let start = std::cmp::max(&self.start, &other.start).clone();
+        let end = std::cmp::min(&self.end, &other.end).clone();
+
+        Some(Span::new(start, end))
+    }
+}
+
+impl<T: Discrete> Span<T> {
+    /// Returns a span covering both spans, or `None` if there would be
+    /// a gap between them
+    ///
+    /// Spans that overlap or sit right next to each other can be joined.
+    /// An empty span joins with anything, leaving the other span as it
+    /// is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::Span;
+    ///
+    /// let span = Span::new(1, 5);
+    /// assert_eq!(
+    ///     span.union_if_touching(&Span::new(3, 9)),
+    ///     Some(Span::new(1, 9))
+    /// );
+    /// assert_eq!(
+    ///     span.union_if_touching(&Span::new(6, 9)),
+    ///     Some(Span::new(1, 9))
+    /// );
+    /// assert_eq!(span.union_if_touching(&Span::new(7, 9)), None);
+    /// ```
+    pub fn union_if_touching(&self, other: &Self) -> Option<Self> {
+        if other.is_empty() {
+            return Some(self.clone());
+        }
+        if self.is_empty() {
+            return Some(other.clone());
+        }
+
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let touching =
+            second.start <= first.end || first.end.successor().as_ref() == Some(&second.start);
+        if !touching {
+            return None;
+        }
+
+        let end = std::cmp::max(&first.end, &second.end).clone();
+
+        Some(Span::new(first.start.clone(), end))
+    }
+
+    pub(crate) fn from_bounds<R>(range: &R) -> Option<Self>
+    where
+        R: RangeBounds<T>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(s) => s.clone(),
+            Bound::Excluded(s) => s.successor()?,
+            Bound::Unbounded => T::MIN,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(e) => e.clone(),
+            Bound::Excluded(e) => e.predecessor()?,
+            Bound::Unbounded => T::MAX,
+        };
+
+        if start <= end {
+            Some(Span::new(start, end))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of values in the span
+    ///
+    /// Spans of every `u128` or `i128` hold one more value than a
+    /// `u128` can count, so their length saturates at `u128::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::Span;
+    ///
+    /// assert_eq!(Span::new(1, 5).len(), 5);
+    /// assert_eq!(Span::new(0u8, 255).len(), 256);
+    /// assert_eq!(Span::new(5, 1).len(), 0);
+    /// assert_eq!(Span::new(0, u128::MAX).len(), u128::MAX);
+    /// ```
+    pub fn len(&self) -> u128 {
+        if self.is_empty() {
+            0
+        } else {
+            self.start.distance(&self.end).saturating_add(1)
+        }
+    }
+}
+
+impl<T> RangeBounds<T> for Span<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        Bound::Included(&self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        Bound::Included(&self.end)
+    }
+}
+
+impl<T: Discrete> From<ops::RangeInclusive<T>> for Span<T> {
+    fn from(range: ops::RangeInclusive<T>) -> Self {
+        let empty = range.is_empty();
+        let (start, end) = range.into_inner();
+
+        // Iterating a range to the end leaves both ends in place and only
+        // marks it exhausted, so the span has to be emptied by hand.
+        if empty && start <= end {
+            if let Some(after) = end.successor() {
+                return Span::new(after, end);
+            }
+            if let Some(before) = start.predecessor() {
+                return Span::new(start, before);
+            }
+        }
+
+        Span::new(start, end)
+    }
+}
+
+impl<T: Discrete> From<ops::Range<T>> for Span<T> {
+    fn from(range: ops::Range<T>) -> Self {
+        match range.end.predecessor() {
+            Some(end) => Span::new(range.start, end),
+            // Nothing comes before the end, so the range is empty, and
+            // any span starting after the end is too.
+            None => Span::new(range.end.successor().unwrap_or(range.start), range.end),
+        }
+    }
+}
+
+impl<T> From<Span<T>> for ops::RangeInclusive<T> {
+    fn from(span: Span<T>) -> Self {
+        span.start..=span.end
+    }
+}
+
+/// Converts a span into a half-open `Range`
+///
+/// This fails if the span ends at the largest value of its type, as
+/// there is nothing after it for the `Range` to end at. The span is
+/// given back as the error.
+impl<T: Discrete> TryFrom<Span<T>> for ops::Range<T> {
+    type Error = Span<T>;
+
+    fn try_from(span: Span<T>) -> Result<Self, Self::Error> {
+        match span.end.successor() {
+            Some(end) => Ok(span.start..end),
+            None => Err(span),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::span::Span;
+use std::convert::TryFrom;
+use std::ops;
+
+#[test]
+fn empty_spans() {
+    assert!(!Span::new(3, 3).is_empty());
+    assert!(Span::new(3, 2).is_empty());
+    assert_eq!(Span::new(3, 2).len(), 0);
+    assert!(!Span::new(3, 2).contains(&3));
+}
+
+#[test]
+fn len_at_type_limits() {
+    assert_eq!(Span::new(0u8, 255).len(), 256);
+    assert_eq!(Span::new(-128i8, 127).len(), 256);
+    assert_eq!(Span::new(0u64, u64::MAX).len(), 1 << 64);
+}
+
+#[test]
+fn overlaps_with_empty_span() {
+    let (start, end) = (4, 2);
+
+    assert!(!Span::new(0, 9).overlaps(&Span::new(start, end)));
+    assert!(!Span::new(start, end).overlaps(&Span::new(0, 9)));
+    assert_eq!(Span::new(0, 9).intersection(&Span::new(start, end)), None);
+}
+
+#[test]
+fn intersection_of_spans() {
+    let span = Span::new(3, 7);
+
+    assert_eq!(span.intersection(&Span::new(0, 2)), None);
+    assert_eq!(span.intersection(&Span::new(0, 3)), Some(Span::new(3, 3)));
+    assert_eq!(span.intersection(&Span::new(4, 5)), Some(Span::new(4, 5)));
+    assert_eq!(span.intersection(&Span::new(0, 9)), Some(Span::new(3, 7)));
+    assert_eq!(span.intersection(&Span::new(7, 9)), Some(Span::new(7, 7)));
+    assert_eq!(span.intersection(&Span::new(8, 9)), None);
+}
+
+#[test]
+fn union_if_touching_spans() {
+    let span = Span::new(3, 7);
+
+    assert_eq!(span.union_if_touching(&Span::new(0, 1)), None);
+    assert_eq!(
+        span.union_if_touching(&Span::new(0, 2)),
+        Some(Span::new(0, 7))
+    );
+    assert_eq!(
+        span.union_if_touching(&Span::new(4, 5)),
+        Some(Span::new(3, 7))
+    );
+    assert_eq!(
+        span.union_if_touching(&Span::new(8, 9)),
+        Some(Span::new(3, 9))
+    );
+    assert_eq!(span.union_if_touching(&Span::new(9, 9)), None);
+    assert_eq!(
+        Span::new(0u8, 254).union_if_touching(&Span::new(255, 255)),
+        Some(Span::new(0, 255))
+    );
+}
+
+#[test]
+fn union_if_touching_empty_span() {
+    let (start, end) = (20, 10);
+
+    assert_eq!(
+        Span::new(3, 7).union_if_touching(&Span::new(start, end)),
+        Some(Span::new(3, 7))
+    );
+    assert_eq!(
+        Span::new(start, end).union_if_touching(&Span::new(3, 7)),
+        Some(Span::new(3, 7))
+    );
+}
+
+#[test]
+fn from_std_ranges() {
+    assert_eq!(Span::from(2..=5), Span::new(2, 5));
+    assert_eq!(Span::from(2..6), Span::new(2, 5));
+    assert!(Span::from(2..2).is_empty());
+    assert!(Span::from(0u8..0).is_empty());
+
+    let (start, end) = (5u8, 0);
+    assert!(Span::from(start..end).is_empty());
+}
+
+#[test]
+fn from_exhausted_range_inclusive() {
+    let mut range = 1..=1;
+    range.next();
+    assert!(Span::from(range).is_empty());
+
+    let mut range = 250u8..=255;
+    range.by_ref().for_each(drop);
+    assert!(Span::from(range).is_empty());
+
+    let mut range = 0u8..=0;
+    range.next_back();
+    assert_eq!(Span::from(range).len(), 0);
+}
+
+#[test]
+fn into_std_ranges() {
+    assert_eq!(ops::RangeInclusive::from(Span::new(2, 5)), 2..=5);
+    assert_eq!(ops::Range::try_from(Span::new(2, 5)), Ok(2..6));
+    assert_eq!(
+        ops::Range::try_from(Span::new(2u8, 255)),
+        Err(Span::new(2, 255))
+    );
+}
+
+#[test]
+fn span_as_range_bounds() {
+    let span = Span::new(2, 5);
+    let values: Vec<_> = (0..10)
+        .filter(|v| ops::RangeBounds::contains(&span, v))
+        .collect();
+
+    assert_eq!(values, vec![2, 3, 4, 5]);
+}
@@ -0,0 +1,304 @@
+#[cfg(test)]
+mod tests;
+
+use crate::discrete::Discrete;
+use crate::set::RangedSet;
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+/// Writes the set in compact range notation
+///
+/// Each range of values is written as `start-end` and each lone value
+/// as `value`, separated by commas: `1-5,7,10-20`. The alternate form
+/// (`{:#}`) uses Rust's range syntax instead: `1..=5, 7, 10..=20`.
+///
+/// Characters in a value that would otherwise end it when it's parsed
+/// again, like the `-` or `,` in a `RangedSet<char>`, are escaped with
+/// a backslash, so the `char`s `,` and `a` to `z` are written as
+/// `\,,a-z`.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::RangedSet;
+///
+/// let set: RangedSet<i32> = vec![1..=5, 7..=7, -20..=-10].into_iter().collect();
+///
+/// assert_eq!(set.to_string(), "-20--10,1-5,7");
+/// assert_eq!(format!("{:#}", set), "-20..=-10, 1..=5, 7");
+///
+/// let chars: RangedSet<char> = vec!['-'..='-', 'a'..='c'].into_iter().collect();
+/// assert_eq!(chars.to_string(), r"\-,a-c");
+/// ```
+impl<T: Discrete + fmt::Display> fmt::Display for RangedSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (separator, to) = if f.alternate() {
+            (", ", "..=")
+        } else {
+            (",", "-")
+        };
+
+        let mut text = String::new();
+        for (index, range) in self.ranges().enumerate() {
+            if index > 0 {
+                f.write_str(separator)?;
+            }
+
+            let (start, end) = range.into_inner();
+            write_value(f, &mut text, &start)?;
+            if start != end {
+                f.write_str(to)?;
+                write_value(f, &mut text, &end)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a value, escaping every character in it that `Parser::value`
+/// wouldn't take as part of the value. `text` is a buffer to reuse.
+fn write_value<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    text: &mut String,
+    value: &T,
+) -> fmt::Result {
+    text.clear();
+    write!(text, "{}", value)?;
+
+    for (index, c) in text.char_indices() {
+        let sign = index == 0 && c == '-' && text.len() > 1;
+        if !(sign || is_value_char(c)) {
+            f.write_char('\\')?;
+        }
+        f.write_char(c)?;
+    }
+
+    Ok(())
+}
+
+fn is_value_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '+' || c == '_'
+}
+
+/// Reads a set written in compact range notation
+///
+/// This accepts everything the `Display` implementation writes, in
+/// either form. Ranges can be written as `start-end` or `start..=end`,
+/// whitespace is allowed around values and separators, and the ranges
+/// can be in any order and overlap each other. A `-` directly in front of a
+/// value is its sign, so `-5--3` is the range from -5 to -3. A
+/// backslash makes the character after it part of the value, so
+/// `\--\.` is the range of `char`s from `-` to `.`.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::RangedSet;
+///
+/// let set: RangedSet<i32> = "10-20, 1-5 ,7,3..=6".parse().unwrap();
+/// assert_eq!(set.to_string(), "1-7,10-20");
+///
+/// let error = "1-5,7,x".parse::<RangedSet<i32>>().unwrap_err();
+/// assert_eq!(error.position(), 6);
+/// ```
+impl<T: Discrete + FromStr> FromStr for RangedSet<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut parser = Parser {
+            input: s,
+            position: 0,
+        };
+        let mut set = RangedSet::new();
+
+        parser.skip_whitespace();
+        if parser.at_end() {
+            return Ok(set);
+        }
+
+        loop {
+            parser.skip_whitespace();
+            let position = parser.position;
+            let start: T = parser.value()?;
+
+            parser.skip_whitespace();
+            let end = if parser.eat("..=") || parser.eat("-") {
+                parser.skip_whitespace();
+                parser.value()?
+            } else {
+                start.clone()
+            };
+
+            if end < start {
+                return Err(ParseError::new(position, ParseErrorKind::InvertedRange));
+            }
+            set.extend(Some(start..=end));
+
+            parser.skip_whitespace();
+            match parser.peek() {
+                Some(',') => parser.position += 1,
+                Some(c) => {
+                    let kind = ParseErrorKind::UnexpectedCharacter(c);
+                    return Err(ParseError::new(parser.position, kind));
+                }
+                None => return Ok(set),
+            }
+        }
+    }
+}
+
+/// Walks through the string being parsed, keeping track of the byte
+/// offset for errors.
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn at_end(&self) -> bool {
+        self.position == self.input.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let rest = self.rest();
+
+        // A leading `-` is the sign of the value, but any other `-` ends
+        // it, like every other character that can't be part of a number
+        // unless it's escaped.
+        let mut token = String::new();
+        let mut chars = rest.char_indices().peekable();
+        if let Some(&(_, '-')) = chars.peek() {
+            token.push('-');
+            chars.next();
+        }
+
+        let mut len = rest.len();
+        while let Some((index, c)) = chars.next() {
+            if c == '\\' {
+                match chars.next() {
+                    Some((_, escaped)) => token.push(escaped),
+                    None => {
+                        let kind = ParseErrorKind::ExpectedValue;
+                        return Err(ParseError::new(self.position + rest.len(), kind));
+                    }
+                }
+            } else if is_value_char(c) {
+                token.push(c);
+            } else {
+                len = index;
+                break;
+            }
+        }
+        if token.is_empty() || (token == "-" && len == 1) {
+            return Err(ParseError::new(
+                self.position,
+                ParseErrorKind::ExpectedValue,
+            ));
+        }
+
+        let value = token
+            .parse()
+            .map_err(|_| ParseError::new(self.position, ParseErrorKind::InvalidValue))?;
+
+        self.position += len;
+        Ok(value)
+    }
+}
+
+/// An error returned when parsing a [`RangedSet`] from a string fails
+///
+/// The error records what went wrong and the byte offset in the string
+/// where it happened.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::{ParseErrorKind, RangedSet};
+///
+/// let error = "1-5, 9-7".parse::<RangedSet<i32>>().unwrap_err();
+///
+/// assert_eq!(error.position(), 5);
+/// assert_eq!(error.kind(), &ParseErrorKind::InvertedRange);
+/// assert_eq!(
+///     error.to_string(),
+///     "range ends before it starts at position 5"
+/// );
+/// ```
+///
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    position: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    fn new(position: usize, kind: ParseErrorKind) -> Self {
+        ParseError { position, kind }
+    }
+
+    /// Returns the byte offset in the string where the error was found
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns what went wrong
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::ExpectedValue => f.write_str("expected a value")?,
+            ParseErrorKind::InvalidValue => f.write_str("invalid value")?,
+            ParseErrorKind::InvertedRange => f.write_str("range ends before it starts")?,
+            ParseErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected {:?}", c)?,
+        }
+
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+/// The ways parsing a [`RangedSet`] from a string can fail
+///
+/// [`RangedSet`]: struct.RangedSet.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// A value was missing, like after a trailing comma
+    ExpectedValue,
+    /// A value couldn't be parsed, like `x` or `300` for a `u8`
+    InvalidValue,
+    /// A range's end was before its start, like `5-1`
+    InvertedRange,
+    /// Something other than a comma came after a value or range
+    UnexpectedCharacter(char),
+}
@@ -0,0 +1,148 @@
+use crate::set::RangedSet;
+use crate::text::{ParseError, ParseErrorKind};
+
+fn parse(s: &str) -> Result<RangedSet<i32>, ParseError> {
+    s.parse()
+}
+
+fn error(position: usize, kind: ParseErrorKind) -> Result<RangedSet<i32>, ParseError> {
+    Err(ParseError { position, kind })
+}
+
+#[test]
+fn display_empty_set() {
+    let rs: RangedSet<i32> = RangedSet::new();
+
+    assert_eq!(rs.to_string(), "");
+    assert_eq!(format!("{:#}", rs), "");
+}
+
+#[test]
+fn display_mixed_set() {
+    let rs: RangedSet<i32> = vec![-8..=-5, -1..=1, 3..=3, 10..=20].into_iter().collect();
+
+    assert_eq!(rs.to_string(), "-8--5,-1-1,3,10-20");
+    assert_eq!(format!("{:#}", rs), "-8..=-5, -1..=1, 3, 10..=20");
+}
+
+#[test]
+fn parse_empty_string() {
+    assert_eq!(parse(""), Ok(RangedSet::new()));
+    assert_eq!(parse("  \t "), Ok(RangedSet::new()));
+}
+
+#[test]
+fn parse_round_trips_display() {
+    let rs: RangedSet<i32> = vec![-8..=-5, -1..=1, 3..=3, 10..=20].into_iter().collect();
+
+    assert_eq!(parse(&rs.to_string()), Ok(rs.clone()));
+    assert_eq!(parse(&format!("{:#}", rs)), Ok(rs));
+}
+
+#[test]
+fn parse_with_whitespace() {
+    let expected: RangedSet<i32> = vec![1..=5, 7..=7].into_iter().collect();
+
+    assert_eq!(parse(" 1 - 5 , 7 "), Ok(expected.clone()));
+    assert_eq!(parse("1..=5,\n7"), Ok(expected));
+}
+
+#[test]
+fn parse_unsorted_and_overlapping() {
+    let expected: RangedSet<i32> = vec![0..=12].into_iter().collect();
+
+    assert_eq!(parse("10-12,0-5,3-9,4"), Ok(expected));
+}
+
+#[test]
+fn parse_negative_values() {
+    let expected: RangedSet<i32> = vec![-10..=-7, -3..=2].into_iter().collect();
+
+    assert_eq!(parse("-10--7,-3-2"), Ok(expected.clone()));
+    assert_eq!(parse("-10 - -7, -3 - 2"), Ok(expected.clone()));
+    assert_eq!(parse("-10..=-7,-3..=2"), Ok(expected));
+}
+
+#[test]
+fn parse_at_type_limits() {
+    let rs: RangedSet<u8> = "0-255".parse().unwrap();
+    assert_eq!(rs.len(), 256);
+
+    let error = "0-256".parse::<RangedSet<u8>>().unwrap_err();
+    assert_eq!(error.position(), 2);
+    assert_eq!(error.kind(), &ParseErrorKind::InvalidValue);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(parse(","), error(0, ParseErrorKind::ExpectedValue));
+    assert_eq!(parse("1,"), error(2, ParseErrorKind::ExpectedValue));
+    assert_eq!(parse("1, ,2"), error(3, ParseErrorKind::ExpectedValue));
+    assert_eq!(parse("1-"), error(2, ParseErrorKind::ExpectedValue));
+    assert_eq!(parse("-"), error(0, ParseErrorKind::ExpectedValue));
+    assert_eq!(parse("1,x"), error(2, ParseErrorKind::InvalidValue));
+    assert_eq!(parse("1,2x"), error(2, ParseErrorKind::InvalidValue));
+    assert_eq!(parse("1, 7-3"), error(3, ParseErrorKind::InvertedRange));
+    assert_eq!(
+        parse("1 2"),
+        error(2, ParseErrorKind::UnexpectedCharacter('2'))
+    );
+    assert_eq!(
+        parse("1;2"),
+        error(1, ParseErrorKind::UnexpectedCharacter(';'))
+    );
+    assert_eq!(
+        parse("1..5"),
+        error(1, ParseErrorKind::UnexpectedCharacter('.'))
+    );
+}
+
+#[test]
+fn parse_error_messages() {
+    assert_eq!(
+        parse("1,").unwrap_err().to_string(),
+        "expected a value at position 2"
+    );
+    assert_eq!(
+        parse("1;2").unwrap_err().to_string(),
+        "unexpected ';' at position 1"
+    );
+}
+
+#[test]
+fn display_escapes_char_separators() {
+    let rs: RangedSet<char> = vec!['-', ',', ' ', '.', '\\', 'a', 'b', 'c', 'x']
+        .into_iter()
+        .collect();
+
+    assert_eq!(rs.to_string(), r"\ ,\,-\.,\\,a-c,x");
+    assert_eq!(format!("{:#}", rs), r"\ , \,..=\., \\, a..=c, x");
+}
+
+#[test]
+fn parse_round_trips_chars() {
+    let rs: RangedSet<char> = vec!['\0'..='\u{7f}', 'é'..='ö', '\u{d7ff}'..='\u{e000}']
+        .into_iter()
+        .collect();
+
+    assert_eq!(rs.to_string().parse(), Ok(rs.clone()));
+    assert_eq!(format!("{:#}", rs).parse(), Ok(rs));
+
+    for c in (0..=0x7f).filter_map(char::from_u32) {
+        let rs: RangedSet<char> = vec![c].into_iter().collect();
+        assert_eq!(rs.to_string().parse(), Ok(rs.clone()), "{:?}", c);
+
+        let rs: RangedSet<char> = vec!['-'..=c, c..=c, c..='~'].into_iter().collect();
+        assert_eq!(rs.to_string().parse(), Ok(rs.clone()), "{:?}", c);
+        assert_eq!(format!("{:#}", rs).parse(), Ok(rs), "{:?}", c);
+    }
+}
+
+#[test]
+fn parse_escapes() {
+    let rs: RangedSet<char> = r"\--\., a".parse().unwrap();
+    assert_eq!(rs.ranges().collect::<Vec<_>>(), vec!['-'..='.', 'a'..='a']);
+
+    let error = r"a,\".parse::<RangedSet<char>>().unwrap_err();
+    assert_eq!(error.kind(), &ParseErrorKind::ExpectedValue);
+}
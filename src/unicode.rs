@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests;
+
+use crate::set::RangedSet;
+use std::fmt::Write;
+
+impl RangedSet<char> {
+    /// Creates a set from a table of inclusive `char` ranges
+    ///
+    /// The table has the same shape as the Unicode property tables
+    /// generated by `ucd-generate` and used by `regex-syntax`, so a
+    /// general category or script can be loaded straight from one. The
+    /// ranges can be in any order and overlap each other, and inverted
+    /// ranges are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// const IDENT_START: &[(char, char)] = &[('A', 'Z'), ('_', '_'), ('a', 'z')];
+    ///
+    /// let set = RangedSet::from_table(IDENT_START);
+    /// assert_eq!(set.len(), 53);
+    /// assert!(set.contains(&'_'));
+    /// ```
+    pub fn from_table(table: &[(char, char)]) -> RangedSet<char> {
+        table.iter().map(|&(start, end)| start..=end).collect()
+    }
+
+    /// Writes the set as a regular expression character class
+    ///
+    /// Letters, digits and punctuation in ASCII are written as they are,
+    /// with the characters that mean something inside a class escaped by
+    /// a backslash. Everything else, including whitespace and control
+    /// characters, is written as a `\u{...}` escape. An empty set is
+    /// written as a class that matches nothing, since `[]` isn't valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let set = RangedSet::from_table(&[('a', 'z'), ('\u{100}', '\u{17f}'), ('-', '-')]);
+    /// assert_eq!(set.to_regex_class(), r"[\-a-z\u{100}-\u{17F}]");
+    ///
+    /// assert_eq!(RangedSet::new().to_regex_class(), r"[^\u{0}-\u{10FFFF}]");
+    /// ```
+    pub fn to_regex_class(&self) -> String {
+        if self.is_empty() {
+            return String::from(r"[^\u{0}-\u{10FFFF}]");
+        }
+
+        let mut class = String::from("[");
+        for range in self.ranges() {
+            let (start, end) = range.into_inner();
+
+            push_class_char(&mut class, start);
+            if start != end {
+                class.push('-');
+                push_class_char(&mut class, end);
+            }
+        }
+        class.push(']');
+
+        class
+    }
+}
+
+/// Characters with a meaning of their own inside a character class
+const CLASS_META: &str = r"\[]^-&~";
+
+fn push_class_char(class: &mut String, c: char) {
+    if CLASS_META.contains(c) {
+        class.push('\\');
+        class.push(c);
+    } else if c.is_ascii_graphic() {
+        class.push(c);
+    } else {
+        // Writing to a `String` can't fail
+        let _ = write!(class, "\\u{{{:X}}}", c as u32);
+    }
+}
@@ -0,0 +1,40 @@
+use crate::set::RangedSet;
+
+#[test]
+fn from_table_merges_ranges() {
+    let set = RangedSet::from_table(&[('x', 'z'), ('a', 'f'), ('d', 'w'), ('9', '0')]);
+
+    assert_eq!(set.ranges().collect::<Vec<_>>(), vec!['a'..='z']);
+    assert!(RangedSet::from_table(&[]).is_empty());
+}
+
+#[test]
+fn from_table_across_surrogates() {
+    let set = RangedSet::from_table(&[('\u{d000}', '\u{d7ff}'), ('\u{e000}', '\u{efff}')]);
+
+    assert_eq!(set.range_count(), 1);
+    assert_eq!(set.len(), 0x800 + 0x1000);
+    assert_eq!(set.iter().nth(0x800), Some('\u{e000}'));
+    assert_eq!(set.iter().rev().nth(0x1000), Some('\u{d7ff}'));
+}
+
+#[test]
+fn regex_class_escapes_meta_characters() {
+    let set: RangedSet<char> = r"\[]^-&~".chars().collect();
+
+    assert_eq!(set.to_regex_class(), r"[\&\-\[-\^\~]");
+}
+
+#[test]
+fn regex_class_escapes_non_graphic_characters() {
+    let set = RangedSet::from_table(&[('\0', ' '), ('!', '!'), ('\u{7f}', '\u{7f}')]);
+
+    assert_eq!(set.to_regex_class(), r"[\u{0}-!\u{7F}]");
+}
+
+#[test]
+fn regex_class_of_everything() {
+    let set = RangedSet::<char>::new().complement();
+
+    assert_eq!(set.to_regex_class(), r"[\u{0}-\u{10FFFF}]");
+}
@@ -0,0 +1,561 @@
+#[cfg(test)]
+mod tests;
+
+use crate::algebra::{Difference, Intersection, Spans, SymmetricDifference, Union};
+use crate::binary::DecodeError;
+use crate::discrete::Discrete;
+use crate::encodable::Encodable;
+use crate::iter::{Values, size_hint};
+use crate::set::RangedSet;
+use crate::span::Span;
+use std::io::{self, Write};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::{fmt, ops, slice};
+
+impl<T: Encodable> RangedSet<T> {
+    /// Writes the ranges of the set as fixed-width pairs
+    ///
+    /// Each range is written as its start and end, in order, both as
+    /// little-endian integers `T::WIDTH` bytes wide. Nothing else is
+    /// written: there's no header or length, so the output can be
+    /// embedded in a larger file. This is the layout a
+    /// [`RangedSetRef`] reads.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSet;
+    ///
+    /// let set: RangedSet<u16> = vec![1..=5, 7..=7].into_iter().collect();
+    ///
+    /// let mut bytes = Vec::new();
+    /// set.encode_pairs_to(&mut bytes).unwrap();
+    /// assert_eq!(bytes, [1, 0, 5, 0, 7, 0, 7, 0]);
+    /// ```
+    ///
+    /// [`RangedSetRef`]: struct.RangedSetRef.html
+    pub fn encode_pairs_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut pair = vec![0; 2 * T::WIDTH];
+
+        for range in self.ranges() {
+            let (start, end) = pair.split_at_mut(T::WIDTH);
+            range.start().write_le(start);
+            range.end().write_le(end);
+
+            writer.write_all(&pair)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A read-only view of a set stored in a buffer of bytes
+///
+/// The buffer holds the ranges of the set as sorted `[start, end]`
+/// pairs of fixed-width integers, as written by [`encode_pairs_to`].
+/// Since every pair takes up the same number of bytes, lookups binary
+/// search the buffer where it is and nothing is decoded or allocated
+/// up front, so a set in a memory-mapped file or shared memory can be
+/// queried in place.
+///
+/// The buffer is checked once, when the view is made, so it can be
+/// trusted by every query after that. Checking it reads every pair,
+/// so a buffer known to be valid, like a large file written by
+/// [`encode_pairs_to`], can skip that with [`new_unchecked`] and only
+/// touch the pages each query needs.
+///
+/// # Example
+///
+/// ```rust
+/// use ranged_set::{RangedSet, RangedSetRef};
+///
+/// let set: RangedSet<u32> = vec![0..=99, 200..=299].into_iter().collect();
+/// let mut bytes = Vec::new();
+/// set.encode_pairs_to(&mut bytes).unwrap();
+///
+/// let view = RangedSetRef::<u32>::new(&bytes).unwrap();
+/// assert_eq!(view.contains(&50), true);
+/// assert_eq!(view.contains(&150), false);
+///
+/// let other: RangedSet<u32> = vec![90..=209].into_iter().collect();
+/// let shared: Vec<_> = view.intersection(&other).collect();
+/// assert_eq!(shared, vec![90..=99, 200..=209]);
+/// ```
+///
+/// [`encode_pairs_to`]: struct.RangedSet.html#method.encode_pairs_to
+/// [`new_unchecked`]: #method.new_unchecked
+pub struct RangedSetRef<'a, T> {
+    bytes: &'a [u8],
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: Encodable> RangedSetRef<'a, T> {
+    /// Returns a view of the set stored in a buffer
+    ///
+    /// The buffer has to hold a whole number of pairs, every value has
+    /// to fit in `T`, and the ranges have to be sorted with a gap
+    /// between each of them, as [`encode_pairs_to`] writes them. The
+    /// whole buffer is read to check this, but nothing is allocated.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::{DecodeError, RangedSetRef};
+    ///
+    /// let view = RangedSetRef::<u8>::new(&[1, 5, 7, 7]).unwrap();
+    /// assert_eq!(view.range_count(), 2);
+    ///
+    /// let overlapping = RangedSetRef::<u8>::new(&[1, 5, 4, 7]);
+    /// assert!(matches!(overlapping, Err(DecodeError::NonCanonical)));
+    ///
+    /// let odd = RangedSetRef::<u8>::new(&[1, 5, 7]);
+    /// assert!(matches!(odd, Err(DecodeError::Truncated)));
+    /// ```
+    ///
+    /// [`encode_pairs_to`]: struct.RangedSet.html#method.encode_pairs_to
+    pub fn new(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        if bytes.len() % (2 * T::WIDTH) != 0 {
+            return Err(DecodeError::Truncated);
+        }
+
+        let mut previous: Option<T> = None;
+        for pair in bytes.chunks_exact(2 * T::WIDTH) {
+            let (start, end) = pair.split_at(T::WIDTH);
+            let start = T::read_le(start).ok_or(DecodeError::OutOfRange)?;
+            let end = T::read_le(end).ok_or(DecodeError::OutOfRange)?;
+
+            let after_previous = match previous {
+                Some(previous) => previous.successor().map_or(false, |next| next < start),
+                None => true,
+            };
+            if end < start || !after_previous {
+                return Err(DecodeError::NonCanonical);
+            }
+
+            previous = Some(end);
+        }
+
+        Ok(RangedSetRef {
+            bytes,
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns a view of the set stored in a buffer without checking
+    /// it
+    ///
+    /// This takes constant time and doesn't read the buffer at all, so
+    /// queries only touch the parts of the buffer they need.
+    ///
+    /// # Safety
+    ///
+    /// The buffer has to be one [`new`] would accept: a whole number of
+    /// pairs, every value in the domain of `T`, and the ranges sorted
+    /// with a gap between each of them. Queries on a view of any other
+    /// buffer may panic or return wrong answers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::{RangedSet, RangedSetRef};
+    ///
+    /// let set: RangedSet<u32> = vec![0..=99, 200..=299].into_iter().collect();
+    /// let mut bytes = Vec::new();
+    /// set.encode_pairs_to(&mut bytes).unwrap();
+    ///
+    /// // The bytes were just written by `encode_pairs_to`
+    /// let view = unsafe { RangedSetRef::<u32>::new_unchecked(&bytes) };
+    /// assert_eq!(view.contains(&250), true);
+    /// ```
+    ///
+    /// [`new`]: #method.new
+    pub unsafe fn new_unchecked(bytes: &'a [u8]) -> Self {
+        RangedSetRef {
+            bytes,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the buffer the view reads from
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns `true` if the set contains no values
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSetRef;
+    ///
+    /// assert_eq!(RangedSetRef::<u8>::new(&[]).unwrap().is_empty(), true);
+    /// assert_eq!(RangedSetRef::<u8>::new(&[1, 1]).unwrap().is_empty(), false);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the number of contiguous ranges of values in the set
+    pub fn range_count(&self) -> usize {
+        self.bytes.len() / (2 * T::WIDTH)
+    }
+
+    /// Returns the number of values in the set
+    ///
+    /// This adds up the length of every range, so it takes time
+    /// proportional to the number of ranges.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSetRef;
+    ///
+    /// let view = RangedSetRef::<u8>::new(&[1, 5, 7, 7]).unwrap();
+    /// assert_eq!(view.len(), 6);
+    /// ```
+    pub fn len(&self) -> u128 {
+        self.pairs()
+            .map(|span| span.len())
+            .fold(0, u128::saturating_add)
+    }
+
+    /// Returns `true` if the set contains a value
+    ///
+    /// The buffer is binary searched, so only a few pairs are read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSetRef;
+    ///
+    /// let view = RangedSetRef::<u8>::new(&[1, 5, 7, 7]).unwrap();
+    /// assert_eq!(view.contains(&0), false);
+    /// assert_eq!(view.contains(&3), true);
+    /// assert_eq!(view.contains(&6), false);
+    /// assert_eq!(view.contains(&7), true);
+    /// ```
+    pub fn contains(&self, value: &T) -> bool {
+        // Find the first range that doesn't end before the value; it's
+        // the only one that can hold it.
+        let (mut low, mut high) = (0, self.range_count());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.value(2 * middle + 1) < *value {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+
+        low < self.range_count() && self.value(2 * low) <= *value
+    }
+
+    /// Returns an iterator over the values in the set, in ascending
+    /// order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSetRef;
+    ///
+    /// let view = RangedSetRef::<u8>::new(&[1, 3, 7, 7]).unwrap();
+    ///
+    /// let values: Vec<_> = view.iter().collect();
+    /// assert_eq!(values, vec![1, 2, 3, 7]);
+    /// ```
+    pub fn iter(&self) -> RefIter<'a, T> {
+        RefIter {
+            pairs: self.pairs(),
+            front: Values::empty(),
+            back: Values::empty(),
+        }
+    }
+
+    /// Returns an iterator over the contiguous ranges of values in the
+    /// set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSetRef;
+    ///
+    /// let view = RangedSetRef::<u8>::new(&[1, 3, 7, 7]).unwrap();
+    ///
+    /// let ranges: Vec<_> = view.ranges().collect();
+    /// assert_eq!(ranges, vec![1..=3, 7..=7]);
+    /// ```
+    pub fn ranges(&self) -> RefRanges<'a, T> {
+        RefRanges {
+            pairs: self.pairs(),
+        }
+    }
+
+    /// Returns the values in either the view or a set as ranges
+    ///
+    /// This works like [`RangedSet::union`], reading the view's ranges
+    /// straight from its buffer.
+    ///
+    /// [`RangedSet::union`]: struct.RangedSet.html#method.union
+    pub fn union<'b>(&self, other: &'b RangedSet<T>) -> Union<'b, T>
+    where
+        'a: 'b,
+    {
+        Union::new(self.spans(), other.spans())
+    }
+
+    /// Returns the values in both the view and a set as ranges
+    ///
+    /// This works like [`RangedSet::intersection`], reading the view's
+    /// ranges straight from its buffer.
+    ///
+    /// [`RangedSet::intersection`]: struct.RangedSet.html#method.intersection
+    pub fn intersection<'b>(&self, other: &'b RangedSet<T>) -> Intersection<'b, T>
+    where
+        'a: 'b,
+    {
+        Intersection::new(self.spans(), other.spans())
+    }
+
+    /// Returns the values in the view but not a set as ranges
+    ///
+    /// This works like [`RangedSet::difference`], reading the view's
+    /// ranges straight from its buffer.
+    ///
+    /// [`RangedSet::difference`]: struct.RangedSet.html#method.difference
+    pub fn difference<'b>(&self, other: &'b RangedSet<T>) -> Difference<'b, T>
+    where
+        'a: 'b,
+    {
+        Difference::new(self.spans(), other.spans())
+    }
+
+    /// Returns the values in exactly one of the view and a set as
+    /// ranges
+    ///
+    /// This works like [`RangedSet::symmetric_difference`], reading the
+    /// view's ranges straight from its buffer.
+    ///
+    /// [`RangedSet::symmetric_difference`]: struct.RangedSet.html#method.symmetric_difference
+    pub fn symmetric_difference<'b>(&self, other: &'b RangedSet<T>) -> SymmetricDifference<'b, T>
+    where
+        'a: 'b,
+    {
+        SymmetricDifference::new(self.spans(), other.spans())
+    }
+
+    /// Copies the set out of the buffer into an owned `RangedSet`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ranged_set::RangedSetRef;
+    ///
+    /// let view = RangedSetRef::<u8>::new(&[1, 3, 7, 7]).unwrap();
+    /// let set = view.to_set();
+    ///
+    /// assert_eq!(set.to_string(), "1-3,7");
+    /// ```
+    pub fn to_set(&self) -> RangedSet<T> {
+        RangedSet::from_canonical_ranges(self.ranges())
+    }
+
+    fn pairs(&self) -> Pairs<'a, T> {
+        Pairs {
+            chunks: self.bytes.chunks_exact(2 * T::WIDTH),
+            read: read_checked,
+        }
+    }
+
+    fn spans(&self) -> Spans<'a, T> {
+        Spans::Pairs(self.pairs())
+    }
+
+    /// Returns the value at an index into the buffer, counting starts
+    /// and ends alike
+    fn value(&self, index: usize) -> T {
+        read_checked(&self.bytes[index * T::WIDTH..][..T::WIDTH])
+    }
+}
+
+fn read_checked<T: Encodable>(bytes: &[u8]) -> T {
+    T::read_le(bytes).expect("the buffer was checked when the view was made")
+}
+
+impl<'a, T> Clone for RangedSetRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for RangedSetRef<'a, T> {}
+
+impl<'a, T: Encodable + fmt::Debug> fmt::Debug for RangedSetRef<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ranges: Vec<_> = self.ranges().collect();
+
+        f.debug_struct("RangedSetRef")
+            .field("ranges", &ranges)
+            .finish()
+    }
+}
+
+impl<'a, T: Encodable> IntoIterator for RangedSetRef<'a, T> {
+    type IntoIter = RefIter<'a, T>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Reads the pairs of a buffer as spans
+///
+/// The reader is a function pointer rather than a trait bound so the
+/// sweeps in `algebra` can walk a view's spans without knowing how they
+/// are stored.
+#[derive(Clone)]
+pub(crate) struct Pairs<'a, T> {
+    chunks: slice::ChunksExact<'a, u8>,
+    read: fn(&[u8]) -> T,
+}
+
+// Function pointers taking a reference only implement `Debug` on newer
+// compilers, so the reader is left out.
+impl<'a, T> fmt::Debug for Pairs<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pairs")
+            .field("chunks", &self.chunks)
+            .finish()
+    }
+}
+
+impl<'a, T> Pairs<'a, T> {
+    fn span(&self, pair: &[u8]) -> Span<T> {
+        let (start, end) = pair.split_at(pair.len() / 2);
+
+        Span::new((self.read)(start), (self.read)(end))
+    }
+}
+
+impl<'a, T> Iterator for Pairs<'a, T> {
+    type Item = Span<T>;
+
+    fn next(&mut self) -> Option<Span<T>> {
+        let pair = self.chunks.next()?;
+
+        Some(self.span(pair))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Pairs<'a, T> {
+    fn next_back(&mut self) -> Option<Span<T>> {
+        let pair = self.chunks.next_back()?;
+
+        Some(self.span(pair))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Pairs<'a, T> {}
+
+/// An iterator over the values of a [`RangedSetRef`]
+///
+/// This `struct` is created by the [`iter`] method on
+/// [`RangedSetRef`].
+///
+/// [`iter`]: struct.RangedSetRef.html#method.iter
+/// [`RangedSetRef`]: struct.RangedSetRef.html
+#[derive(Clone, Debug)]
+pub struct RefIter<'a, T: Discrete> {
+    pairs: Pairs<'a, T>,
+    front: Values<T>,
+    back: Values<T>,
+}
+
+impl<'a, T: Encodable> Iterator for RefIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(value) = self.front.next() {
+                return Some(value);
+            }
+
+            match self.pairs.next() {
+                Some(span) => self.front = Values::spanning(span),
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pairs = self
+            .pairs
+            .clone()
+            .map(|span| span.len())
+            .fold(0, u128::saturating_add);
+
+        size_hint(
+            self.front
+                .len()
+                .saturating_add(pairs)
+                .saturating_add(self.back.len()),
+        )
+    }
+}
+
+impl<'a, T: Encodable> DoubleEndedIterator for RefIter<'a, T> {
+    fn next_back(&mut self) -> Option<T> {
+        loop {
+            if let Some(value) = self.back.next_back() {
+                return Some(value);
+            }
+
+            match self.pairs.next_back() {
+                Some(span) => self.back = Values::spanning(span),
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<'a, T: Encodable> FusedIterator for RefIter<'a, T> {}
+
+/// An iterator over the contiguous ranges of values in a
+/// [`RangedSetRef`]
+///
+/// This `struct` is created by the [`ranges`] method on
+/// [`RangedSetRef`].
+///
+/// [`ranges`]: struct.RangedSetRef.html#method.ranges
+/// [`RangedSetRef`]: struct.RangedSetRef.html
+#[derive(Clone, Debug)]
+pub struct RefRanges<'a, T: Discrete> {
+    pairs: Pairs<'a, T>,
+}
+
+impl<'a, T: Discrete> Iterator for RefRanges<'a, T> {
+    type Item = ops::RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pairs.next().map(ops::RangeInclusive::from)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pairs.size_hint()
+    }
+}
+
+impl<'a, T: Discrete> DoubleEndedIterator for RefRanges<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.pairs.next_back().map(ops::RangeInclusive::from)
+    }
+}
+
+impl<'a, T: Discrete> ExactSizeIterator for RefRanges<'a, T> {}
+
+impl<'a, T: Discrete> FusedIterator for RefRanges<'a, T> {}
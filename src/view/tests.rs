@@ -0,0 +1,185 @@
+use crate::binary::DecodeError;
+use crate::set::RangedSet;
+use crate::view::RangedSetRef;
+
+fn pairs(rs: &RangedSet<i32>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    rs.encode_pairs_to(&mut bytes).unwrap();
+    bytes
+}
+
+fn sample() -> RangedSet<i32> {
+    vec![-20..=-10, -3..=-3, 0..=4, 9..=9, 15..=30]
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn view_of_empty_buffer() {
+    let view = RangedSetRef::<i32>::new(&[]).unwrap();
+
+    assert!(view.is_empty());
+    assert_eq!(view.range_count(), 0);
+    assert_eq!(view.len(), 0);
+    assert!(!view.contains(&0));
+    assert_eq!(view.iter().next(), None);
+    assert_eq!(view.to_set(), RangedSet::new());
+}
+
+#[test]
+fn encode_pairs_layout() {
+    let rs: RangedSet<i32> = vec![-2..=1, 7..=7].into_iter().collect();
+
+    assert_eq!(
+        pairs(&rs),
+        [254, 255, 255, 255, 1, 0, 0, 0, 7, 0, 0, 0, 7, 0, 0, 0]
+    );
+}
+
+#[test]
+fn view_round_trips_set() {
+    let rs = sample();
+    let bytes = pairs(&rs);
+    let view = RangedSetRef::<i32>::new(&bytes).unwrap();
+
+    assert_eq!(view.as_bytes(), &bytes[..]);
+    assert_eq!(view.range_count(), rs.range_count());
+    assert_eq!(view.len(), rs.len());
+    assert_eq!(view.to_set(), rs);
+}
+
+#[test]
+fn contains_matches_set() {
+    let rs = sample();
+    let bytes = pairs(&rs);
+    let view = RangedSetRef::<i32>::new(&bytes).unwrap();
+
+    for value in -25..=35 {
+        assert_eq!(view.contains(&value), rs.contains(&value), "{}", value);
+    }
+}
+
+#[test]
+fn iterators_match_set() {
+    let rs = sample();
+    let bytes = pairs(&rs);
+    let view = RangedSetRef::<i32>::new(&bytes).unwrap();
+
+    assert!(view.iter().eq(rs.iter()));
+    assert!(view.iter().rev().eq(rs.iter().rev()));
+    assert!(view.ranges().eq(rs.ranges()));
+    assert!(view.ranges().rev().eq(rs.ranges().rev()));
+    assert_eq!(view.iter().size_hint(), rs.iter().size_hint());
+    assert_eq!(view.ranges().len(), 5);
+
+    let mut iter = view.iter();
+    assert_eq!(iter.next(), Some(-20));
+    assert_eq!(iter.next_back(), Some(30));
+    assert_eq!(
+        iter.size_hint(),
+        (rs.len() as usize - 2, Some(rs.len() as usize - 2))
+    );
+}
+
+#[test]
+fn algebra_matches_set() {
+    let rs = sample();
+    let bytes = pairs(&rs);
+    let view = RangedSetRef::<i32>::new(&bytes).unwrap();
+
+    let others: Vec<RangedSet<i32>> = vec![
+        RangedSet::new(),
+        vec![-100..=100].into_iter().collect(),
+        vec![-15..=-2, 5..=8, 10..=14, 31..=40]
+            .into_iter()
+            .collect(),
+        vec![-3..=-3, 2..=9, 20..=20].into_iter().collect(),
+    ];
+
+    for other in &others {
+        assert!(view.union(other).eq(rs.union(other)));
+        assert!(view.intersection(other).eq(rs.intersection(other)));
+        assert!(view.difference(other).eq(rs.difference(other)));
+        assert!(
+            view.symmetric_difference(other)
+                .eq(rs.symmetric_difference(other))
+        );
+    }
+}
+
+#[test]
+fn new_rejects_partial_pairs() {
+    let bytes = pairs(&sample());
+
+    for len in 1..8 {
+        let result = RangedSetRef::<i32>::new(&bytes[..bytes.len() - len]);
+        assert!(matches!(result, Err(DecodeError::Truncated)), "{}", len);
+    }
+}
+
+#[test]
+fn new_rejects_non_canonical_pairs() {
+    let cases: [&[u8]; 5] = [
+        // Ends before it starts
+        &[5, 1],
+        // Out of order
+        &[7, 9, 1, 5],
+        // Overlapping
+        &[1, 5, 5, 9],
+        // Adjacent
+        &[1, 5, 6, 9],
+        // Anything after the largest value
+        &[1, 255, 0, 0],
+    ];
+
+    for bytes in cases.iter() {
+        let result = RangedSetRef::<u8>::new(bytes);
+        assert!(
+            matches!(result, Err(DecodeError::NonCanonical)),
+            "{:?}",
+            bytes
+        );
+    }
+
+    assert!(RangedSetRef::<u8>::new(&[1, 5, 7, 255]).is_ok());
+}
+
+#[test]
+fn debug_lists_ranges() {
+    let view = RangedSetRef::<u8>::new(&[1, 5, 7, 7]).unwrap();
+
+    assert_eq!(
+        format!("{:?}", view),
+        "RangedSetRef { ranges: [1..=5, 7..=7] }"
+    );
+}
+
+#[test]
+fn new_unchecked_reads_valid_buffer() {
+    let rs = sample();
+    let bytes = pairs(&rs);
+    let view = unsafe { RangedSetRef::<i32>::new_unchecked(&bytes) };
+
+    assert_eq!(view.to_set(), rs);
+    assert!(view.contains(&-3));
+    assert!(!view.contains(&5));
+    assert!(view.iter().eq(rs.iter()));
+}
+
+#[test]
+fn view_of_chars() {
+    let rs: RangedSet<char> = vec!['a'..='f', '\u{d7ff}'..='\u{e000}']
+        .into_iter()
+        .collect();
+    let mut bytes = Vec::new();
+    rs.encode_pairs_to(&mut bytes).unwrap();
+
+    let view = RangedSetRef::<char>::new(&bytes).unwrap();
+    assert_eq!(view.len(), 8);
+    assert!(view.contains(&'\u{e000}'));
+    assert_eq!(view.to_set(), rs);
+
+    // A range ending on the surrogate U+D800
+    let result = RangedSetRef::<char>::new(&[0x61, 0, 0, 0, 0x00, 0xd8, 0, 0]);
+    assert!(matches!(result, Err(DecodeError::OutOfRange)));
+}